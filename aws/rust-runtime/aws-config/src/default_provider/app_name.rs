@@ -68,12 +68,20 @@ impl Builder {
     /// Build an [`AppName`] from the default chain
     pub async fn app_name(self) -> Option<AppName> {
         let env = self.provider_config.env();
-        let profiles = self.provider_config.profile().await;
+        match AppName::from_env(&env) {
+            Ok(Some(app_name)) => return Some(app_name),
+            Ok(None) => {}
+            Err(err) => {
+                tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for App Name setting");
+                return None;
+            }
+        }
 
-        let standard = EnvConfigValue::new()
-            .env("AWS_SDK_UA_APP_ID")
-            .profile("sdk_ua_app_id")
-            .validate(&env, profiles, |name| AppName::new(name.to_string()));
+        let profiles = self.provider_config.profile().await;
+        let standard =
+            EnvConfigValue::new()
+                .profile("sdk_ua_app_id")
+                .validate(&env, profiles, |name| AppName::new(name.to_string()));
         let with_fallback = match standard {
             Ok(None) => self.fallback_app_name().await,
             other => other,
@@ -116,6 +124,26 @@ mod tests {
         assert_eq!(Some(AppName::new("correct").unwrap()), app_name);
     }
 
+    #[tokio::test]
+    async fn invalid_env_does_not_fall_back_to_profile() {
+        let fs = Fs::from_slice(&[("test_config", "[default]\nsdk_ua_app_id = profile")]);
+        let env = Env::from_slice(&[
+            ("AWS_CONFIG_FILE", "test_config"),
+            ("AWS_SDK_UA_APP_ID", "invalid app name"),
+        ]);
+        let app_name = Builder::default()
+            .configure(
+                &ProviderConfig::no_configuration()
+                    .with_fs(fs)
+                    .with_env(env)
+                    .with_http_client(no_traffic_client()),
+            )
+            .app_name()
+            .await;
+
+        assert_eq!(None, app_name);
+    }
+
     // test that overriding profile_name on the root level is deprecated
     #[tokio::test]
     async fn profile_name_override() {
@@ -3,10 +3,10 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
-use crate::environment::parse_url;
 use crate::provider_config::ProviderConfig;
 use aws_runtime::env_config::EnvConfigValue;
 use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_types::endpoint_config::{EndpointUrl, InvalidEndpoint};
 use aws_types::origin::Origin;
 
 mod env {
@@ -31,7 +31,7 @@ pub async fn endpoint_url_provider(provider_config: &ProviderConfig) -> Option<S
     EnvConfigValue::new()
         .env(env::ENDPOINT_URL)
         .profile(profile_key::ENDPOINT_URL)
-        .validate(&env, profiles, parse_url)
+        .validate(&env, profiles, parse_endpoint_url)
         .map_err(
             |err| tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for endpoint URL setting"),
         )
@@ -54,13 +54,17 @@ pub async fn endpoint_url_provider_with_origin(
     EnvConfigValue::new()
         .env(env::ENDPOINT_URL)
         .profile(profile_key::ENDPOINT_URL)
-        .validate_and_return_origin(&env, profiles, parse_url)
+        .validate_and_return_origin(&env, profiles, parse_endpoint_url)
         .map_err(
             |err| tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for endpoint URL setting"),
         )
         .unwrap_or_default()
 }
 
+fn parse_endpoint_url(value: &str) -> Result<String, InvalidEndpoint> {
+    EndpointUrl::new(value).map(|url| url.as_str().to_owned())
+}
+
 #[cfg(test)]
 mod test {
     use super::endpoint_url_provider;
@@ -81,6 +85,16 @@ mod test {
         assert!(logs_contain(env::ENDPOINT_URL));
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn log_error_on_url_without_scheme() {
+        let conf = ProviderConfig::empty()
+            .with_env(Env::from_slice(&[(env::ENDPOINT_URL, "localhost:4566")]));
+        assert_eq!(None, endpoint_url_provider(&conf).await);
+        assert!(logs_contain("invalid value for endpoint URL setting"));
+        assert!(logs_contain("missing a scheme"));
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn environment_priority() {
@@ -59,26 +59,3 @@ pub(crate) fn parse_uint(value: &str) -> Result<u32, InvalidUintValue> {
         value: value.to_string(),
     })
 }
-
-#[derive(Debug)]
-pub(crate) struct InvalidUrlValue {
-    value: String,
-}
-
-impl fmt::Display for InvalidUrlValue {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} is not a valid URL", self.value)
-    }
-}
-
-impl Error for InvalidUrlValue {}
-
-pub(crate) fn parse_url(value: &str) -> Result<String, InvalidUrlValue> {
-    match url::Url::parse(value) {
-        // We discard the parse result because it includes a trailing slash
-        Ok(_) => Ok(value.to_string()),
-        Err(_) => Err(InvalidUrlValue {
-            value: value.to_string(),
-        }),
-    }
-}
@@ -181,6 +181,8 @@ enum SigV4SigningError {
     MissingSigningRegion,
     #[cfg(feature = "sigv4a")]
     MissingSigningRegionSet,
+    #[cfg(feature = "sigv4a")]
+    InvalidSigningRegionSet(aws_sigv4::sign::v4a::InvalidRegionSet),
     MissingSigningName,
     WrongIdentityType(Identity),
     BadTypeInEndpointAuthSchemeConfig(&'static str),
@@ -195,6 +197,8 @@ impl fmt::Display for SigV4SigningError {
             MissingSigningRegion => w("missing signing region"),
             #[cfg(feature = "sigv4a")]
             MissingSigningRegionSet => w("missing signing region set"),
+            #[cfg(feature = "sigv4a")]
+            InvalidSigningRegionSet(err) => write!(f, "{err}"),
             MissingSigningName => w("missing signing name"),
             WrongIdentityType(identity) => {
                 write!(f, "wrong identity type for SigV4/sigV4a. Expected AWS credentials but got `{identity:?}`")
@@ -96,7 +96,9 @@ impl SigV4aSigner {
                     .region_set
                     .as_ref()
                     .ok_or(SigV4SigningError::MissingSigningRegionSet)?
-                    .as_ref(),
+                    .as_ref()
+                    .try_into()
+                    .map_err(SigV4SigningError::InvalidSigningRegionSet)?,
             )
             .name(
                 operation_config
@@ -214,6 +216,7 @@ impl Sign for SigV4aSigner {
 #[cfg(test)]
 mod tests {
     use super::{SigV4OperationSigningConfig, SigV4aSigner, EXPIRATION_WARNING};
+    use crate::auth::SigV4SigningError;
     use crate::auth::{HttpSignatureType, SigningOptions};
     use aws_credential_types::Credentials;
     use aws_sigv4::http_request::SigningSettings;
@@ -268,6 +271,24 @@ mod tests {
         assert!(logs_contain(EXPIRATION_WARNING));
     }
 
+    #[test]
+    fn empty_region_set_is_rejected() {
+        let identity = Credentials::for_tests().into();
+        let operation_config = SigV4OperationSigningConfig {
+            region_set: Some("".into()),
+            name: Some(SigningName::from_static("test")),
+            ..Default::default()
+        };
+        let err = SigV4aSigner::signing_params(
+            SigningSettings::default(),
+            &identity,
+            &operation_config,
+            SystemTime::UNIX_EPOCH,
+        )
+        .expect_err("empty region set is invalid");
+        assert!(matches!(err, SigV4SigningError::InvalidSigningRegionSet(_)));
+    }
+
     #[test]
     fn endpoint_config_overrides_region_and_service() {
         let mut layer = Layer::new("test");
@@ -511,15 +511,7 @@ struct OsMetadata {
 
 impl fmt::Display for OsMetadata {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let os_family = match self.os_family {
-            OsFamily::Windows => "windows",
-            OsFamily::Linux => "linux",
-            OsFamily::Macos => "macos",
-            OsFamily::Android => "android",
-            OsFamily::Ios => "ios",
-            OsFamily::Other => "other",
-        };
-        write!(f, "os/{}", os_family)?;
+        write!(f, "os/{}", self.os_family)?;
         if let Some(ref version) = self.version {
             write!(f, "/{}", version)?;
         }
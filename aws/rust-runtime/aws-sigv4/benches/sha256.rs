@@ -0,0 +1,39 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_sigv4::sign::v4::sha256_hardware_accelerated;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use sha2::{Digest, Sha256};
+use std::hint::black_box;
+
+// Compare the two implementations by running this once as-is and once with
+// `--features sha256-force-soft`; the benchmark is named after the implementation in use.
+pub fn sha256_large_payload(c: &mut Criterion) {
+    let payload = vec![0x5a_u8; 8 * 1024 * 1024];
+    let implementation = if sha256_hardware_accelerated() {
+        "hardware"
+    } else {
+        "portable"
+    };
+
+    let mut group = c.benchmark_group("sha256_large_payload");
+    group.throughput(Throughput::Bytes(payload.len() as u64));
+    group.bench_with_input(
+        BenchmarkId::from_parameter(implementation),
+        &payload,
+        |b, payload| b.iter(|| hex::encode(Sha256::digest(black_box(payload)))),
+    );
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+
+    config = Criterion::default();
+
+    targets = sha256_large_payload
+}
+
+criterion_main!(benches);
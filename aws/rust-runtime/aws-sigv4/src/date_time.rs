@@ -7,7 +7,7 @@
 #![allow(dead_code)]
 
 use std::time::SystemTime;
-use time::{OffsetDateTime, Time};
+use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
 
 /// Truncates the subseconds from the given `SystemTime` to zero.
 pub(crate) fn truncate_subsecs(time: SystemTime) -> SystemTime {
@@ -45,6 +45,35 @@ pub(crate) fn format_date_time(time: SystemTime) -> String {
     )
 }
 
+/// Parses a date in the `YYYYMMDD'T'HHMMSS'Z'` format of [`format_date_time`], such as the
+/// `X-Amz-Date` of a presigned request.
+pub(crate) fn parse_amz_date_time(date_time: &str) -> Option<SystemTime> {
+    let bytes = date_time.as_bytes();
+    if bytes.len() != 16 || bytes[8] != b'T' || bytes[15] != b'Z' {
+        return None;
+    }
+    let number = |start: usize, end: usize| -> Option<u16> {
+        let digits = date_time.get(start..end)?;
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        digits.parse().ok()
+    };
+    let date = Date::from_calendar_date(
+        number(0, 4)?.into(),
+        Month::try_from(number(4, 6)? as u8).ok()?,
+        number(6, 8)? as u8,
+    )
+    .ok()?;
+    let time = Time::from_hms(
+        number(9, 11)? as u8,
+        number(11, 13)? as u8,
+        number(13, 15)? as u8,
+    )
+    .ok()?;
+    Some(PrimitiveDateTime::new(date, time).assume_utc().into())
+}
+
 /// Parse functions that are only needed for unit tests.
 #[cfg(test)]
 pub(crate) mod test_parsers {
@@ -129,6 +158,26 @@ mod tests {
         assert_eq!("20150830T123600Z", format_date_time(time));
     }
 
+    #[test]
+    fn amz_date_time_parsing() {
+        assert_eq!(
+            Some(parse_date_time("20150830T123600Z").unwrap()),
+            parse_amz_date_time("20150830T123600Z")
+        );
+        for invalid in [
+            "",
+            "20150830T123600",
+            "20150830 123600Z",
+            "2015083OT123600Z",
+            "20151330T123600Z",
+            "20150830T253600Z",
+            "+0150830T123600Z",
+            "2015é30T123600Z",
+        ] {
+            assert_eq!(None, parse_amz_date_time(invalid), "`{invalid}`");
+        }
+    }
+
     #[test]
     fn date_roundtrip() {
         let time = parse_date("20150830").unwrap();
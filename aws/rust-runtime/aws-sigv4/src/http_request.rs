@@ -104,6 +104,68 @@ impl<'a> From<v4a::SigningParams<'a, SigningSettings>> for SigningParams<'a> {
 }
 
 impl<'a> SigningParams<'a> {
+    /// Creates signing params, picking the signing algorithm based on `regions`.
+    ///
+    /// `regions` is either a single region, or a comma-separated region set such as
+    /// `us-east-1,us-west-2` or the `*` wildcard. A single region is signed for with SigV4, and
+    /// anything else with SigV4a, since only SigV4a signatures can be valid in more than one
+    /// region. Returns an error if `regions` isn't a valid region set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use aws_credential_types::Credentials;
+    /// use aws_sigv4::http_request::{SigningParams, SigningSettings};
+    /// use aws_sigv4::SignatureVersion;
+    /// use aws_smithy_runtime_api::client::identity::Identity;
+    /// use std::time::SystemTime;
+    ///
+    /// let identity: Identity = Credentials::new(
+    ///     "AKIDEXAMPLE",
+    ///     "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+    ///     None,
+    ///     None,
+    ///     "hardcoded-credentials",
+    /// ).into();
+    /// let params = SigningParams::for_regions(
+    ///     &identity,
+    ///     "us-east-1,us-west-2",
+    ///     "exampleservice",
+    ///     SystemTime::now(),
+    ///     SigningSettings::default(),
+    /// ).unwrap();
+    /// assert_eq!(SignatureVersion::V4a, params.signature_version());
+    /// ```
+    #[cfg(feature = "sigv4a")]
+    pub fn for_regions(
+        identity: &'a aws_smithy_runtime_api::client::identity::Identity,
+        regions: &'a str,
+        name: &'a str,
+        time: SystemTime,
+        settings: SigningSettings,
+    ) -> Result<Self, v4a::InvalidRegionSet> {
+        let region_set = v4a::RegionSet::parse(regions)?;
+        let mut explicit_regions = region_set.regions();
+        if let (Some(_), None) = (explicit_regions.next(), explicit_regions.next()) {
+            // Every region in `regions` is the same one, so the first is the region to sign for.
+            let region = regions.split(',').next().unwrap_or_default().trim();
+            return Ok(Self::V4(v4::SigningParams {
+                identity,
+                region,
+                name,
+                time,
+                settings,
+            }));
+        }
+        Ok(Self::V4a(v4a::SigningParams {
+            identity,
+            region_set,
+            name,
+            time,
+            settings,
+        }))
+    }
+
     /// Return the credentials within the signing params.
     pub(crate) fn credentials(&self) -> Result<&Credentials, SigningError> {
         let identity = match self {
@@ -130,7 +192,7 @@ impl<'a> SigningParams<'a> {
     /// If the signing params are for SigV4a, return the region set. Otherwise, return `None`.
     pub fn region_set(&self) -> Option<&str> {
         match self {
-            SigningParams::V4a(v4a::SigningParams { region_set, .. }) => Some(region_set),
+            SigningParams::V4a(v4a::SigningParams { region_set, .. }) => Some(region_set.as_str()),
             _ => None,
         }
     }
@@ -39,17 +39,21 @@ pub(crate) mod param {
     pub(crate) const X_AMZ_CREDENTIAL: &str = "X-Amz-Credential";
     pub(crate) const X_AMZ_DATE: &str = "X-Amz-Date";
     pub(crate) const X_AMZ_EXPIRES: &str = "X-Amz-Expires";
+    pub(crate) const X_AMZ_NONCE: &str = "X-Amz-Nonce";
     pub(crate) const X_AMZ_SECURITY_TOKEN: &str = "X-Amz-Security-Token";
     pub(crate) const X_AMZ_SIGNED_HEADERS: &str = "X-Amz-SignedHeaders";
     pub(crate) const X_AMZ_SIGNATURE: &str = "X-Amz-Signature";
 }
 
-pub(crate) const HMAC_256: &str = "AWS4-HMAC-SHA256";
+pub(crate) use crate::core::HMAC_256;
 
 const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+const REDACTED: &str = "** REDACTED **";
 const STREAMING_UNSIGNED_PAYLOAD_TRAILER: &str = "STREAMING-UNSIGNED-PAYLOAD-TRAILER";
+const STREAMING_AWS4_HMAC_SHA256_PAYLOAD: &str = "STREAMING-AWS4-HMAC-SHA256-PAYLOAD";
+const STREAMING_AWS4_ECDSA_P256_SHA256_PAYLOAD: &str = "STREAMING-AWS4-ECDSA-P256-SHA256-PAYLOAD";
 
-#[derive(Debug, PartialEq)]
+#[derive(PartialEq)]
 pub(crate) struct HeaderValues<'a> {
     pub(crate) content_sha256: Cow<'a, str>,
     pub(crate) date_time: String,
@@ -59,19 +63,52 @@ pub(crate) struct HeaderValues<'a> {
     pub(crate) region_set: Option<&'a str>,
 }
 
-#[derive(Debug, PartialEq)]
+impl<'a> fmt::Debug for HeaderValues<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut values = f.debug_struct("HeaderValues");
+        values
+            .field("content_sha256", &self.content_sha256)
+            .field("date_time", &self.date_time)
+            .field("security_token", &self.security_token.map(|_| REDACTED))
+            .field("signed_headers", &self.signed_headers);
+        #[cfg(feature = "sigv4a")]
+        values.field("region_set", &self.region_set);
+        values.finish()
+    }
+}
+
+#[derive(PartialEq)]
 pub(crate) struct QueryParamValues<'a> {
     pub(crate) algorithm: &'static str,
     pub(crate) content_sha256: Cow<'a, str>,
     pub(crate) credential: String,
     pub(crate) date_time: String,
     pub(crate) expires: String,
+    pub(crate) nonce: Option<&'a str>,
     pub(crate) security_token: Option<&'a str>,
     pub(crate) signed_headers: SignedHeaders,
     #[cfg(feature = "sigv4a")]
     pub(crate) region_set: Option<&'a str>,
 }
 
+impl<'a> fmt::Debug for QueryParamValues<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut values = f.debug_struct("QueryParamValues");
+        values
+            .field("algorithm", &self.algorithm)
+            .field("content_sha256", &self.content_sha256)
+            .field("credential", &self.credential)
+            .field("date_time", &self.date_time)
+            .field("expires", &self.expires)
+            .field("nonce", &self.nonce)
+            .field("security_token", &self.security_token.map(|_| REDACTED))
+            .field("signed_headers", &self.signed_headers);
+        #[cfg(feature = "sigv4a")]
+        values.field("region_set", &self.region_set);
+        values.finish()
+    }
+}
+
 #[derive(Debug, PartialEq)]
 pub(crate) enum SignatureValues<'a> {
     Headers(HeaderValues<'a>),
@@ -108,7 +145,7 @@ impl<'a> SignatureValues<'a> {
     }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(PartialEq)]
 pub(crate) struct CanonicalRequest<'a> {
     pub(crate) method: &'a str,
     pub(crate) path: Cow<'a, str>,
@@ -117,6 +154,41 @@ pub(crate) struct CanonicalRequest<'a> {
     pub(crate) values: SignatureValues<'a>,
 }
 
+impl<'a> fmt::Debug for CanonicalRequest<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let security_token = match &self.values {
+            SignatureValues::Headers(values) => values.security_token,
+            SignatureValues::QueryParams(values) => values.security_token,
+        };
+        // When presigning, the session token is also one of the query params.
+        let params = match (&self.params, security_token) {
+            (Some(params), Some(security_token)) => {
+                let encoded_token = aws_smithy_http::query::fmt_string(security_token);
+                Some(
+                    params
+                        .split('&')
+                        .map(|param| match param.split_once('=') {
+                            Some((name, value)) if value == encoded_token => {
+                                format!("{name}={REDACTED}")
+                            }
+                            _ => param.to_owned(),
+                        })
+                        .collect::<Vec<_>>()
+                        .join("&"),
+                )
+            }
+            (params, _) => params.clone(),
+        };
+        f.debug_struct("CanonicalRequest")
+            .field("method", &self.method)
+            .field("path", &self.path)
+            .field("params", &params)
+            .field("headers", &self.headers)
+            .field("values", &self.values)
+            .finish()
+    }
+}
+
 impl<'a> CanonicalRequest<'a> {
     /// Construct a CanonicalRequest from a [`SignableRequest`] and [`SigningParams`].
     ///
@@ -153,7 +225,7 @@ impl<'a> CanonicalRequest<'a> {
             PercentEncodingMode::Double => Cow::Owned(percent_encode_path(&path)),
             PercentEncodingMode::Single => path,
         };
-        let payload_hash = Self::payload_hash(req.body());
+        let payload_hash = Self::payload_hash(req.body(), params.signature_version());
 
         let date_time = format_date_time(*params.time());
         let (signed_headers, canonical_headers) =
@@ -207,6 +279,7 @@ impl<'a> CanonicalRequest<'a> {
                         .expect("presigning requires expires_in")
                         .as_secs()
                         .to_string(),
+                    nonce: params.settings().presign_nonce.as_deref(),
                     security_token,
                     signed_headers,
                     #[cfg(feature = "sigv4a")]
@@ -305,7 +378,10 @@ impl<'a> CanonicalRequest<'a> {
         Ok((signed_headers, canonical_headers))
     }
 
-    fn payload_hash<'b>(body: &'b SignableBody<'b>) -> Cow<'b, str> {
+    fn payload_hash<'b>(
+        body: &'b SignableBody<'b>,
+        signature_version: SignatureVersion,
+    ) -> Cow<'b, str> {
         // Payload hash computation
         //
         // Based on the input body, set the payload_hash of the canonical request:
@@ -315,6 +391,7 @@ impl<'a> CanonicalRequest<'a> {
         // - use `UnsignedPayload`
         // - use `UnsignedPayload` for streaming requests
         // - use `StreamingUnsignedPayloadTrailer` for streaming requests with trailers
+        // - use the algorithm-specific streaming payload for requests sent in signed chunks
         match body {
             SignableBody::Bytes(data) => Cow::Owned(sha256_hex_string(data)),
             SignableBody::Precomputed(digest) => Cow::Borrowed(digest.as_str()),
@@ -322,6 +399,10 @@ impl<'a> CanonicalRequest<'a> {
             SignableBody::StreamingUnsignedPayloadTrailer => {
                 Cow::Borrowed(STREAMING_UNSIGNED_PAYLOAD_TRAILER)
             }
+            SignableBody::StreamingSignedPayload => match signature_version {
+                SignatureVersion::V4 => Cow::Borrowed(STREAMING_AWS4_HMAC_SHA256_PAYLOAD),
+                SignatureVersion::V4a => Cow::Borrowed(STREAMING_AWS4_ECDSA_P256_SHA256_PAYLOAD),
+            },
         }
     }
 
@@ -340,6 +421,10 @@ impl<'a> CanonicalRequest<'a> {
             add_param(&mut params, param::X_AMZ_DATE, &values.date_time);
             add_param(&mut params, param::X_AMZ_EXPIRES, &values.expires);
 
+            if let Some(nonce) = values.nonce {
+                add_param(&mut params, param::X_AMZ_NONCE, nonce);
+            }
+
             #[cfg(feature = "sigv4a")]
             if let Some(regions) = values.region_set {
                 add_param(&mut params, sigv4a::param::X_AMZ_REGION_SET, regions);
@@ -424,19 +509,21 @@ impl<'a> CanonicalRequest<'a> {
 
 impl<'a> fmt::Display for CanonicalRequest<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "{}", self.method)?;
-        writeln!(f, "{}", self.path)?;
-        writeln!(f, "{}", self.params.as_deref().unwrap_or(""))?;
-        // write out _all_ the headers
-        for header in &self.values.signed_headers().headers {
-            write!(f, "{}:", header.0.as_str())?;
-            writeln!(f, "{}", self.header_values_for(&header.0))?;
-        }
-        writeln!(f)?;
-        // write out the signed headers
-        writeln!(f, "{}", self.values.signed_headers().as_str())?;
-        write!(f, "{}", self.values.content_sha256())?;
-        Ok(())
+        let headers = self
+            .values
+            .signed_headers()
+            .headers
+            .iter()
+            .map(|header| (header.0.as_str(), self.header_values_for(&header.0)));
+        crate::core::write_canonical_request(
+            f,
+            self.method,
+            &self.path,
+            self.params.as_deref().unwrap_or(""),
+            headers,
+            self.values.signed_headers().as_str(),
+            self.values.content_sha256(),
+        )
     }
 }
 
@@ -539,13 +626,11 @@ impl<'a> SigningScope<'a> {
 
 impl<'a> fmt::Display for SigningScope<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}/{}/{}/aws4_request",
-            format_date(self.time),
+        f.write_str(&crate::core::signing_scope(
+            &format_date(self.time),
             self.region,
-            self.service
-        )
+            self.service,
+        ))
     }
 }
 
@@ -611,17 +696,16 @@ impl<'a> StringToSign<'a> {
 
 impl<'a> fmt::Display for StringToSign<'a> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}\n{}\n{}\n{}",
+        let scope = match self.signature_version {
+            SignatureVersion::V4 => self.scope.to_string(),
+            SignatureVersion::V4a => self.scope.v4a_display(),
+        };
+        f.write_str(&crate::core::string_to_sign(
             self.algorithm,
-            format_date_time(self.time),
-            match self.signature_version {
-                SignatureVersion::V4 => self.scope.to_string(),
-                SignatureVersion::V4a => self.scope.v4a_display(),
-            },
-            self.hashed_creq
-        )
+            &format_date_time(self.time),
+            &scope,
+            self.hashed_creq,
+        ))
     }
 }
 
@@ -633,8 +717,8 @@ mod tests {
     };
     use crate::http_request::test;
     use crate::http_request::{
-        PayloadChecksumKind, SessionTokenMode, SignableBody, SignableRequest, SignatureLocation,
-        SigningParams, SigningSettings,
+        PayloadChecksumKind, PercentEncodingMode, SessionTokenMode, SignableBody, SignableRequest,
+        SignatureLocation, SigningParams, SigningSettings, UriPathNormalizationMode,
     };
     use crate::sign::v4;
     use crate::sign::v4::sha256_hex_string;
@@ -690,6 +774,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_repeated_header_values_are_normalized_individually() {
+        let mut req = test::v4::test_request("get-vanilla-query-order-key-case");
+        // Repeated headers are joined in the order they were given, whatever the case of their
+        // names, and each value is trimmed and has its sequential spaces collapsed on its own.
+        req.headers
+            .push(("X-Amz-Meta-Foo".to_string(), "  first   value ".to_string()));
+        req.headers
+            .push(("x-amz-meta-foo".to_string(), "second\tvalue".to_string()));
+        req.headers
+            .push(("x-amz-meta-FOO".to_string(), " \tthird ".to_string()));
+        let req = SignableRequest::from(&req);
+        let settings = SigningSettings {
+            session_token_mode: SessionTokenMode::Exclude,
+            ..Default::default()
+        };
+        let identity = Credentials::for_tests().into();
+        let signing_params = signing_params(&identity, settings);
+        let creq = CanonicalRequest::from(&req, &signing_params).unwrap();
+
+        assert_eq!(
+            creq.values.signed_headers().to_string(),
+            "host;x-amz-date;x-amz-meta-foo"
+        );
+        // Only spaces are trimmed and collapsed, so tabs are left as they are.
+        assert_eq!(
+            creq.header_values_for("x-amz-meta-foo"),
+            "first value,second\tvalue,\tthird",
+        );
+        assert!(creq
+            .to_string()
+            .contains("\nx-amz-meta-foo:first value,second\tvalue,\tthird\n"));
+    }
+
+    #[test]
+    fn test_header_value_surrounding_spaces_are_trimmed() {
+        let mut req = test::v4::test_request("get-vanilla-query-order-key-case");
+        req.headers
+            .push(("x-amz-meta-foo".to_string(), "   value   ".to_string()));
+        let req = SignableRequest::from(&req);
+        let settings = SigningSettings {
+            session_token_mode: SessionTokenMode::Exclude,
+            ..Default::default()
+        };
+        let identity = Credentials::for_tests().into();
+        let signing_params = signing_params(&identity, settings);
+        let creq = CanonicalRequest::from(&req, &signing_params).unwrap();
+
+        assert_eq!(creq.header_values_for("x-amz-meta-foo"), "value");
+    }
+
     #[test]
     fn test_set_xamz_sha_256() {
         let req = test::v4::test_request("get-vanilla-query-order-key-case");
@@ -807,6 +942,49 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn test_path_with_spaces_and_reserved_characters() {
+        let req = http0::Request::builder()
+            .uri("https://example.amazonaws.com/my%20bucket/a%2Bb/./c/../key%3D1")
+            .body("")
+            .unwrap()
+            .into();
+        let req = SignableRequest::from(&req);
+        let identity = Credentials::for_tests().into();
+
+        // By default the path is normalized and then encoded a second time
+        let params = signing_params(&identity, SigningSettings::default());
+        let creq = CanonicalRequest::from(&req, &params).unwrap();
+        assert_eq!("/my%2520bucket/a%252Bb/key%253D1", creq.path);
+
+        // Services like S3 sign the path just as it will be sent
+        let settings = SigningSettings {
+            percent_encoding_mode: PercentEncodingMode::Single,
+            uri_path_normalization_mode: UriPathNormalizationMode::Disabled,
+            ..Default::default()
+        };
+        let params = signing_params(&identity, settings);
+        let creq = CanonicalRequest::from(&req, &params).unwrap();
+        assert_eq!("/my%20bucket/a%2Bb/./c/../key%3D1", creq.path);
+
+        // Each setting can also be toggled on its own
+        let settings = SigningSettings {
+            percent_encoding_mode: PercentEncodingMode::Single,
+            ..Default::default()
+        };
+        let params = signing_params(&identity, settings);
+        let creq = CanonicalRequest::from(&req, &params).unwrap();
+        assert_eq!("/my%20bucket/a%2Bb/key%3D1", creq.path);
+
+        let settings = SigningSettings {
+            uri_path_normalization_mode: UriPathNormalizationMode::Disabled,
+            ..Default::default()
+        };
+        let params = signing_params(&identity, settings);
+        let creq = CanonicalRequest::from(&req, &params).unwrap();
+        assert_eq!("/my%2520bucket/a%252Bb/./c/../key%253D1", creq.path);
+    }
+
     #[test]
     fn test_tilde_in_uri() {
         let req = http0::Request::builder()
@@ -875,6 +1053,29 @@ mod tests {
         assert_eq!(creq.values.signed_headers().as_str(), "host;x-amz-date");
     }
 
+    #[test]
+    fn test_debug_redacts_session_token() {
+        let req = test::v4::test_request("get-vanilla-query-order-key-case");
+        let req = SignableRequest::from(&req);
+        let identity = Credentials::for_tests_with_session_token().into();
+        for signature_location in [SignatureLocation::Headers, SignatureLocation::QueryParams] {
+            let settings = SigningSettings {
+                signature_location,
+                expires_in: Some(Duration::from_secs(30)),
+                session_token_mode: SessionTokenMode::Include,
+                ..Default::default()
+            };
+            let signing_params = signing_params(&identity, settings);
+            let creq = CanonicalRequest::from(&req, &signing_params).unwrap();
+            let debug = format!("{creq:?}");
+            assert!(!debug.contains("notarealsessiontoken"), "{debug}");
+            assert!(
+                debug.contains("security_token: Some(\"** REDACTED **\")"),
+                "{debug}"
+            );
+        }
+    }
+
     // It should exclude authorization, user-agent, x-amzn-trace-id headers from presigning
     #[test]
     fn non_presigning_header_exclusion() {
@@ -940,6 +1141,39 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "sigv4a")]
+    #[test]
+    fn presign_nonce_is_part_of_canonical_query() {
+        let request = http0::Request::builder()
+            .uri("https://some-endpoint.some-region.amazonaws.com/?foo=bar")
+            .body("")
+            .unwrap()
+            .into();
+        let request = SignableRequest::from(&request);
+
+        let identity = Credentials::for_tests().into();
+        let settings = SigningSettings::default()
+            .with_signature_location(SignatureLocation::QueryParams)
+            .with_expires_in(Duration::from_secs(30))
+            .with_presign_nonce("8e1d2f7c");
+        let signing_params = crate::sign::v4a::SigningParams {
+            identity: &identity,
+            region_set: crate::sign::v4a::RegionSet::parse("us-east-1,us-west-2").unwrap(),
+            name: "service",
+            time: parse_date_time("20150830T123600Z").unwrap(),
+            settings,
+        }
+        .into();
+        let canonical = CanonicalRequest::from(&request, &signing_params).unwrap();
+
+        let params = canonical.params.as_deref().unwrap();
+        assert!(
+            params.contains("&X-Amz-Nonce=8e1d2f7c&"),
+            "nonce must be signed: {params}"
+        );
+        assert!(canonical.to_string().contains("X-Amz-Nonce=8e1d2f7c"));
+    }
+
     #[allow(clippy::ptr_arg)] // The proptest macro requires this arg to be a Vec instead of a slice.
     fn valid_input(input: &Vec<String>) -> bool {
         [
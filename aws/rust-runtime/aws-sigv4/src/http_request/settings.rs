@@ -41,6 +41,67 @@ pub struct SigningSettings {
     /// Some services require an alternative session token header or query param instead of
     /// `x-amz-security-token` or `X-Amz-Security-Token`.
     pub session_token_name_override: Option<&'static str>,
+
+    /// For presigned requests, a caller-supplied nonce to include in the signed query as
+    /// `X-Amz-Nonce`.
+    ///
+    /// A presigned URL can otherwise be replayed for as long as it is valid. When each URL is
+    /// presigned with a unique nonce, the receiver can reject a repeated one with
+    /// [`verify_presigned_nonce`](crate::verify::verify_presigned_nonce).
+    pub presign_nonce: Option<Cow<'static, str>>,
+}
+
+impl SigningSettings {
+    /// Sets how long a presigned request is valid for
+    pub fn with_expires_in(mut self, expires_in: Duration) -> Self {
+        self.expires_in = Some(expires_in);
+        self
+    }
+
+    /// Sets where to put the signature
+    pub fn with_signature_location(mut self, signature_location: SignatureLocation) -> Self {
+        self.signature_location = signature_location;
+        self
+    }
+
+    /// Sets which additional checksum header to add, if any
+    pub fn with_payload_checksum_kind(
+        mut self,
+        payload_checksum_kind: PayloadChecksumKind,
+    ) -> Self {
+        self.payload_checksum_kind = payload_checksum_kind;
+        self
+    }
+
+    /// Sets how to encode the request URL when signing
+    pub fn with_percent_encoding_mode(
+        mut self,
+        percent_encoding_mode: PercentEncodingMode,
+    ) -> Self {
+        self.percent_encoding_mode = percent_encoding_mode;
+        self
+    }
+
+    /// Sets whether the absolute path component of the URI should be normalized during signing
+    pub fn with_uri_path_normalization_mode(
+        mut self,
+        uri_path_normalization_mode: UriPathNormalizationMode,
+    ) -> Self {
+        self.uri_path_normalization_mode = uri_path_normalization_mode;
+        self
+    }
+
+    /// Sets whether the session token should be part of the canonical request
+    pub fn with_session_token_mode(mut self, session_token_mode: SessionTokenMode) -> Self {
+        self.session_token_mode = session_token_mode;
+        self
+    }
+
+    /// Sets the nonce to include in the signed query of a presigned request
+    pub fn with_presign_nonce(mut self, nonce: impl Into<Cow<'static, str>>) -> Self {
+        self.presign_nonce = Some(nonce.into());
+        self
+    }
 }
 
 /// HTTP payload checksum type
@@ -138,6 +199,7 @@ impl Default for SigningSettings {
             uri_path_normalization_mode: UriPathNormalizationMode::Enabled,
             session_token_mode: SessionTokenMode::Include,
             session_token_name_override: None,
+            presign_nonce: None,
         }
     }
 }
@@ -151,3 +213,50 @@ pub enum SignatureLocation {
     /// Place the signature in the request query parameters
     QueryParams,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_methods_set_fields() {
+        let settings = SigningSettings::default()
+            .with_expires_in(Duration::from_secs(60))
+            .with_signature_location(SignatureLocation::QueryParams)
+            .with_payload_checksum_kind(PayloadChecksumKind::XAmzSha256)
+            .with_percent_encoding_mode(PercentEncodingMode::Single)
+            .with_uri_path_normalization_mode(UriPathNormalizationMode::Disabled)
+            .with_session_token_mode(SessionTokenMode::Exclude);
+
+        assert_eq!(Some(Duration::from_secs(60)), settings.expires_in);
+        assert_eq!(SignatureLocation::QueryParams, settings.signature_location);
+        assert_eq!(
+            PayloadChecksumKind::XAmzSha256,
+            settings.payload_checksum_kind
+        );
+        assert_eq!(PercentEncodingMode::Single, settings.percent_encoding_mode);
+        assert_eq!(
+            UriPathNormalizationMode::Disabled,
+            settings.uri_path_normalization_mode
+        );
+        assert_eq!(SessionTokenMode::Exclude, settings.session_token_mode);
+        // Fields that weren't touched keep their defaults
+        assert_eq!(
+            SigningSettings::default().excluded_headers,
+            settings.excluded_headers
+        );
+    }
+
+    #[test]
+    fn cloned_settings_are_independent() {
+        let base =
+            SigningSettings::default().with_signature_location(SignatureLocation::QueryParams);
+        let one_minute = base.clone().with_expires_in(Duration::from_secs(60));
+        let one_hour = base.clone().with_expires_in(Duration::from_secs(3600));
+
+        assert_eq!(None, base.expires_in);
+        assert_eq!(Some(Duration::from_secs(60)), one_minute.expires_in);
+        assert_eq!(Some(Duration::from_secs(3600)), one_hour.expires_in);
+        assert_eq!(one_minute.signature_location, one_hour.signature_location);
+    }
+}
@@ -91,13 +91,44 @@ pub enum SignableBody<'a> {
 
     /// Set when a streaming body has checksum trailers.
     StreamingUnsignedPayloadTrailer,
+
+    /// A streaming body that is sent in signed chunks.
+    ///
+    /// The signature of the request becomes the seed signature for the first chunk. For Sigv4a,
+    /// the chunks can be signed with [`v4a::ChunkSigner`](crate::sign::v4a::ChunkSigner).
+    StreamingSignedPayload,
 }
 
 /// Instructions for applying a signature to an HTTP request.
-#[derive(Debug)]
+///
+/// The [`Debug`] implementation redacts the values of sensitive headers and of the session token
+/// query parameter.
 pub struct SigningInstructions {
     headers: Vec<Header>,
     params: Vec<(&'static str, Cow<'static, str>)>,
+    // The name of the query parameter carrying the session token, if any.
+    sensitive_param: &'static str,
+}
+
+impl Debug for SigningInstructions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let params: Vec<_> = self
+            .params
+            .iter()
+            .map(|(name, value)| {
+                let value = if *name == self.sensitive_param {
+                    "** REDACTED **"
+                } else {
+                    value.as_ref()
+                };
+                (*name, value)
+            })
+            .collect();
+        f.debug_struct("SigningInstructions")
+            .field("headers", &self.headers)
+            .field("params", &params)
+            .finish()
+    }
 }
 
 /// Header representation for use in [`SigningInstructions`]
@@ -140,7 +171,18 @@ impl Header {
 
 impl SigningInstructions {
     fn new(headers: Vec<Header>, params: Vec<(&'static str, Cow<'static, str>)>) -> Self {
-        Self { headers, params }
+        Self {
+            headers,
+            params,
+            sensitive_param: param::X_AMZ_SECURITY_TOKEN,
+        }
+    }
+
+    fn with_sensitive_param(mut self, sensitive_param: Option<&'static str>) -> Self {
+        if let Some(sensitive_param) = sensitive_param {
+            self.sensitive_param = sensitive_param;
+        }
+        self
     }
 
     /// Returns the headers and query params that should be applied to this request
@@ -225,9 +267,11 @@ pub fn sign<'a>(
             ))
         }
         SignatureLocation::QueryParams => {
+            let session_token_name_override = params.settings().session_token_name_override;
             let (params, signature) = calculate_signing_params(&request, params)?;
             Ok(SigningOutput::new(
-                SigningInstructions::new(vec![], params),
+                SigningInstructions::new(vec![], params)
+                    .with_sensitive_param(session_token_name_override),
                 signature,
             ))
         }
@@ -260,12 +304,19 @@ fn calculate_signing_params<'a>(
         }
         #[cfg(feature = "sigv4a")]
         SigningParams::V4a(params) => {
-            let string_to_sign =
-                StringToSign::new_v4a(params.time, params.region_set, params.name, encoded_creq)
-                    .to_string();
+            let string_to_sign = StringToSign::new_v4a(
+                params.time,
+                params.region_set.as_str(),
+                params.name,
+                encoded_creq,
+            )
+            .to_string();
 
-            let secret_key =
-                v4a::generate_signing_key(creds.access_key_id(), creds.secret_access_key());
+            let secret_key = v4a::generate_signing_key_cached(
+                creds.access_key_id(),
+                creds.secret_access_key(),
+                params.time,
+            );
             let signature = v4a::calculate_signature(&secret_key, string_to_sign.as_bytes());
             (signature, string_to_sign)
         }
@@ -285,6 +336,10 @@ fn calculate_signing_params<'a>(
         (param::X_AMZ_SIGNATURE, Cow::Owned(signature.clone())),
     ];
 
+    if let Some(nonce) = values.nonce {
+        signing_params.push((param::X_AMZ_NONCE, Cow::Owned(nonce.to_owned())));
+    }
+
     #[cfg(feature = "sigv4a")]
     if let Some(region_set) = params.region_set() {
         if params.signature_version() == SignatureVersion::V4a {
@@ -385,13 +440,16 @@ fn calculate_signing_headers<'a>(
         SigningParams::V4a(params) => {
             let sts = StringToSign::new_v4a(
                 params.time,
-                params.region_set,
+                params.region_set.as_str(),
                 params.name,
                 encoded_creq.as_str(),
             );
 
-            let signing_key =
-                v4a::generate_signing_key(creds.access_key_id(), creds.secret_access_key());
+            let signing_key = v4a::generate_signing_key_cached(
+                creds.access_key_id(),
+                creds.secret_access_key(),
+                params.time,
+            );
             let signature = v4a::calculate_signature(&signing_key, sts.to_string().as_bytes());
 
             let values = creq.values.as_headers().expect("signing with headers");
@@ -399,7 +457,7 @@ fn calculate_signing_headers<'a>(
             add_header(
                 &mut headers,
                 crate::http_request::canonical_request::sigv4a::header::X_AMZ_REGION_SET,
-                params.region_set,
+                params.region_set.as_str(),
                 false,
             );
 
@@ -426,7 +484,10 @@ fn calculate_signing_headers<'a>(
             if let Some(security_token) = creds.session_token() {
                 add_header(
                     &mut headers,
-                    header::X_AMZ_SECURITY_TOKEN,
+                    params
+                        .settings
+                        .session_token_name_override
+                        .unwrap_or(header::X_AMZ_SECURITY_TOKEN),
                     security_token,
                     true,
                 );
@@ -536,8 +597,9 @@ mod tests {
     mod sigv4a_tests {
         use super::*;
         use crate::http_request::canonical_request::{CanonicalRequest, StringToSign};
-        use crate::http_request::{sign, test, SigningParams};
+        use crate::http_request::{sign, test, PayloadChecksumKind, SigningParams};
         use crate::sign::v4a;
+        use crate::{SignatureVersion, SigningOutput};
         use p256::ecdsa::signature::{Signature, Verifier};
         use p256::ecdsa::{DerSignature, SigningKey};
         use pretty_assertions::assert_eq;
@@ -587,7 +649,7 @@ mod tests {
             let creds = params.credentials().unwrap();
             let signing_key =
                 v4a::generate_signing_key(creds.access_key_id(), creds.secret_access_key());
-            let sig = DerSignature::from_bytes(&hex::decode(out.signature).unwrap()).unwrap();
+            let sig = DerSignature::from_bytes(&hex::decode(&out.signature).unwrap()).unwrap();
             let sig = sig
                 .try_into()
                 .expect("DER-style signatures are always convertible into fixed-size signatures");
@@ -596,6 +658,215 @@ mod tests {
             let peer_public_key = signing_key.verifying_key();
             let sts = actual_string_to_sign.as_bytes();
             peer_public_key.verify(sts, &sig).unwrap();
+
+            assert!(
+                v4a::verify(
+                    &expected_creq,
+                    &out.signature,
+                    creds.access_key_id(),
+                    creds.secret_access_key(),
+                    params.name(),
+                    *params.time(),
+                ),
+                "signature didn't verify against the expected canonical request"
+            );
+        }
+
+        fn run_v4a_presign_test_suite(test_name: &str) {
+            let tc = test::v4a::test_context(test_name);
+            let req = test::v4a::test_request(test_name);
+            let out = v4a::presign(
+                SignableRequest::from(&req),
+                v4a::SigningParams::from(&tc),
+                Duration::from_secs(tc.expiration_in_seconds),
+            )
+            .unwrap();
+
+            assert_eq!(
+                0,
+                out.output.headers().count(),
+                "presigning adds no headers"
+            );
+            let param = |name: &str| {
+                out.output
+                    .params()
+                    .iter()
+                    .find(|(k, _)| *k == name)
+                    .map(|(_, v)| v.as_ref())
+            };
+            assert_eq!(Some("AWS4-ECDSA-P256-SHA256"), param("X-Amz-Algorithm"));
+            assert_eq!(
+                Some("AKIDEXAMPLE/20150830/service/aws4_request"),
+                param("X-Amz-Credential")
+            );
+            assert_eq!(Some("us-east-1"), param("X-Amz-Region-Set"));
+            assert_eq!(Some("3600"), param("X-Amz-Expires"));
+            assert_eq!(Some(out.signature.as_str()), param("X-Amz-Signature"));
+
+            // Sigv4a signatures are non-deterministic, so verify the signature against the
+            // expected string to sign rather than comparing it directly.
+            let expected_string_to_sign =
+                test::v4a::test_string_to_sign(test_name, SignatureLocation::QueryParams);
+            let params = new_v4a_signing_params_from_context(&tc, SignatureLocation::QueryParams);
+            let creds = params.credentials().unwrap();
+            let signing_key =
+                v4a::generate_signing_key(creds.access_key_id(), creds.secret_access_key());
+            let sig = DerSignature::from_bytes(&hex::decode(&out.signature).unwrap()).unwrap();
+            let sig = sig
+                .try_into()
+                .expect("DER-style signatures are always convertible into fixed-size signatures");
+            let signing_key = SigningKey::from_bytes(signing_key.as_ref()).unwrap();
+            signing_key
+                .verifying_key()
+                .verify(expected_string_to_sign.as_bytes(), &sig)
+                .unwrap();
+        }
+
+        #[test]
+        fn test_presign_get_vanilla() {
+            run_v4a_presign_test_suite("get-vanilla");
+        }
+
+        #[test]
+        fn test_presign_get_vanilla_query_order_key_case() {
+            run_v4a_presign_test_suite("get-vanilla-query-order-key-case");
+        }
+
+        #[test]
+        fn test_presign_get_vanilla_with_session_token() {
+            run_v4a_presign_test_suite("get-vanilla-with-session-token");
+        }
+
+        #[test]
+        fn test_presign_post_vanilla_query() {
+            run_v4a_presign_test_suite("post-vanilla-query");
+        }
+
+        fn sign_v4a_headers(
+            test_name: &str,
+            session_token_name_override: Option<&'static str>,
+        ) -> (String, SigningOutput<SigningInstructions>) {
+            let tc = test::v4a::test_context(test_name);
+            let mut params = v4a::SigningParams::from(&tc);
+            params.settings.session_token_name_override = session_token_name_override;
+            let params: SigningParams<'_> = params.into();
+
+            let req = test::v4a::test_request(test_name);
+            let signable_req = SignableRequest::from(&req);
+            let creq = CanonicalRequest::from(&signable_req, &params)
+                .unwrap()
+                .to_string();
+            (creq, sign(signable_req, &params).unwrap())
+        }
+
+        fn header<'a>(out: &'a SigningOutput<SigningInstructions>, name: &str) -> Option<&'a str> {
+            out.output()
+                .headers()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value)
+        }
+
+        #[test]
+        fn test_session_token_is_signed_when_present() {
+            let (creq, out) = sign_v4a_headers("get-vanilla-with-session-token", None);
+            assert_eq!(
+                test::v4a::test_canonical_request(
+                    "get-vanilla-with-session-token",
+                    SignatureLocation::Headers
+                ),
+                creq
+            );
+            assert_eq!(
+                Some("6e86291e8372ff2a2260956d9b8aae1d763fbf315fa00fa31553b73ebf194267"),
+                header(&out, "x-amz-security-token")
+            );
+            assert!(header(&out, "authorization")
+                .unwrap()
+                .contains("SignedHeaders=host;x-amz-date;x-amz-region-set;x-amz-security-token,"));
+        }
+
+        #[test]
+        fn test_session_token_is_omitted_when_absent() {
+            let (creq, out) = sign_v4a_headers("get-vanilla", None);
+            assert_eq!(
+                test::v4a::test_canonical_request("get-vanilla", SignatureLocation::Headers),
+                creq
+            );
+            assert!(!creq.contains("x-amz-security-token"));
+            assert_eq!(None, header(&out, "x-amz-security-token"));
+            assert!(header(&out, "authorization")
+                .unwrap()
+                .contains("SignedHeaders=host;x-amz-date;x-amz-region-set,"));
+        }
+
+        #[test]
+        fn test_session_token_name_override() {
+            let (creq, out) = sign_v4a_headers(
+                "get-vanilla-with-session-token",
+                Some("x-amz-s3session-token"),
+            );
+            assert!(creq.contains(
+                "\nx-amz-s3session-token:6e86291e8372ff2a2260956d9b8aae1d763fbf315fa00fa31553b73ebf194267\n"
+            ));
+            assert!(!creq.contains("x-amz-security-token"));
+            assert_eq!(
+                Some("6e86291e8372ff2a2260956d9b8aae1d763fbf315fa00fa31553b73ebf194267"),
+                header(&out, "x-amz-s3session-token")
+            );
+            assert_eq!(None, header(&out, "x-amz-security-token"));
+        }
+
+        fn assert_v4a_payload_hash(body: SignableBody<'static>, expected_hash: &str) {
+            let tc = test::v4a::test_context("get-vanilla");
+            let mut params = v4a::SigningParams::from(&tc);
+            params.settings.payload_checksum_kind = PayloadChecksumKind::XAmzSha256;
+            let params: SigningParams<'_> = params.into();
+
+            let mut req = test::v4a::test_request("get-vanilla");
+            req.set_body(body);
+            let signable_req = SignableRequest::from(&req);
+            let creq = CanonicalRequest::from(&signable_req, &params)
+                .unwrap()
+                .to_string();
+            assert!(creq.contains(&format!("\nx-amz-content-sha256:{expected_hash}\n")));
+            assert!(creq.ends_with(&format!("\n{expected_hash}")));
+
+            let out = sign(signable_req, &params).unwrap();
+            let content_sha256 = out
+                .output
+                .headers()
+                .find(|(name, _)| *name == "x-amz-content-sha256")
+                .map(|(_, value)| value);
+            assert_eq!(Some(expected_hash), content_sha256);
+
+            let creds = params.credentials().unwrap();
+            assert!(v4a::verify(
+                &creq,
+                out.signature(),
+                creds.access_key_id(),
+                creds.secret_access_key(),
+                params.name(),
+                *params.time(),
+            ));
+        }
+
+        #[test]
+        fn test_payload_hash_precomputed() {
+            let hash = "44ce7dd67c959e0d3524ffac1771dfbba87d2b6b4b4e99e42034a8b803f8b072";
+            assert_v4a_payload_hash(SignableBody::Precomputed(hash.to_owned()), hash);
+        }
+
+        #[test]
+        fn test_payload_hash_unsigned_payload() {
+            assert_v4a_payload_hash(SignableBody::UnsignedPayload, "UNSIGNED-PAYLOAD");
+        }
+
+        #[test]
+        fn test_payload_hash_streaming_signed_payload() {
+            assert_v4a_payload_hash(
+                SignableBody::StreamingSignedPayload,
+                "STREAMING-AWS4-ECDSA-P256-SHA256-PAYLOAD",
+            );
         }
 
         #[test]
@@ -787,6 +1058,92 @@ mod tests {
                 SignatureLocation::QueryParams,
             );
         }
+
+        fn sign_for_regions(regions: &str) -> (SignatureVersion, String) {
+            let identity = &Credentials::for_tests().into();
+            let params = SigningParams::for_regions(
+                identity,
+                regions,
+                "service",
+                parse_date_time("20150830T123600Z").unwrap(),
+                SigningSettings::default(),
+            )
+            .unwrap();
+            let request = test::v4::test_request("get-vanilla");
+            let out = sign(SignableRequest::from(&request), &params).unwrap();
+            let authorization = out
+                .output()
+                .headers()
+                .find(|(name, _)| *name == "authorization")
+                .map(|(_, value)| value.to_owned())
+                .unwrap();
+            (params.signature_version(), authorization)
+        }
+
+        #[test]
+        fn single_region_selects_v4() {
+            for regions in ["us-east-1", " us-east-1 ", "us-east-1,us-east-1"] {
+                let (version, authorization) = sign_for_regions(regions);
+                assert_eq!(SignatureVersion::V4, version, "`{regions}`");
+                assert!(
+                    authorization.starts_with(
+                        "AWS4-HMAC-SHA256 Credential=ANOTREAL/20150830/us-east-1/service/"
+                    ),
+                    "`{regions}`: {authorization}"
+                );
+            }
+        }
+
+        #[test]
+        fn region_set_selects_v4a() {
+            for regions in ["us-east-1,us-west-2", "*"] {
+                let (version, authorization) = sign_for_regions(regions);
+                assert_eq!(SignatureVersion::V4a, version, "`{regions}`");
+                assert!(
+                    authorization.starts_with(
+                        "AWS4-ECDSA-P256-SHA256 Credential=ANOTREAL/20150830/service/"
+                    ),
+                    "`{regions}`: {authorization}"
+                );
+            }
+            let identity = &Credentials::for_tests().into();
+            assert!(SigningParams::for_regions(
+                identity,
+                "",
+                "service",
+                parse_date_time("20150830T123600Z").unwrap(),
+                SigningSettings::default(),
+            )
+            .is_err());
+        }
+
+        #[test]
+        fn presigned_nonce_is_added_to_query() {
+            let settings = SigningSettings::default()
+                .with_signature_location(SignatureLocation::QueryParams)
+                .with_expires_in(Duration::from_secs(30))
+                .with_presign_nonce("8e1d2f7c");
+            let identity = &Credentials::for_tests().into();
+            let params = SigningParams::for_regions(
+                identity,
+                "us-east-1,us-west-2",
+                "service",
+                parse_date_time("20150830T123600Z").unwrap(),
+                settings,
+            )
+            .unwrap();
+            let request = test::v4::test_request("get-vanilla");
+            let out = sign(SignableRequest::from(&request), &params).unwrap();
+
+            let (instructions, _signature) = out.into_parts();
+            let nonces: Vec<_> = instructions
+                .params()
+                .iter()
+                .filter(|(name, _)| *name == "X-Amz-Nonce")
+                .map(|(_, value)| value.as_ref())
+                .collect();
+            assert_eq!(vec!["8e1d2f7c"], nonces);
+        }
     }
 
     #[test]
@@ -1054,6 +1411,38 @@ mod tests {
         }
     }
 
+    #[test]
+    fn signing_instructions_debug_redacts_session_token() {
+        let identity = &Credentials::for_tests_with_session_token().into();
+        for (location, session_token_name_override) in [
+            (SignatureLocation::Headers, None),
+            (SignatureLocation::QueryParams, None),
+            (
+                SignatureLocation::QueryParams,
+                Some("X-Amz-S3session-Token"),
+            ),
+        ] {
+            let mut settings = SigningSettings::default()
+                .with_signature_location(location)
+                .with_expires_in(Duration::from_secs(30));
+            settings.session_token_name_override = session_token_name_override;
+            let params = v4::SigningParams {
+                identity,
+                region: "us-east-1",
+                name: "service",
+                time: parse_date_time("20150830T123600Z").unwrap(),
+                settings,
+            }
+            .into();
+
+            let original = test::v4::test_request("get-vanilla-query-order-key-case");
+            let out = sign(SignableRequest::from(&original), &params).unwrap();
+            let debug = format!("{:?}", out.output);
+            assert!(!debug.contains("notarealsessiontoken"), "{debug}");
+            assert!(debug.contains("REDACTED"), "{debug}");
+        }
+    }
+
     #[test]
     fn apply_signing_instructions_headers() {
         let mut headers = vec![];
@@ -123,7 +123,7 @@ pub(crate) mod v4a {
         fn from(tc: &'a TestContext) -> Self {
             crate::sign::v4a::SigningParams {
                 identity: &tc.identity,
-                region_set: &tc.region,
+                region_set: crate::sign::v4a::RegionSet::parse(&tc.region).unwrap(),
                 name: &tc.service,
                 time: OffsetDateTime::parse(&tc.timestamp, &Rfc3339)
                     .unwrap()
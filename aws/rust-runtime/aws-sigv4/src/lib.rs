@@ -9,6 +9,7 @@
 //! Provides functions for calculating Sigv4 signing keys, signatures, and
 //! optional utilities for signing HTTP requests and Event Stream messages.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![allow(clippy::derive_partial_eq_without_eq)]
 #![warn(
     missing_docs,
@@ -18,10 +19,20 @@
     unreachable_pub
 )]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
 use std::fmt;
 
+pub mod core;
+
+#[cfg(feature = "std")]
 pub mod sign;
 
+#[cfg(feature = "std")]
+pub mod verify;
+
+#[cfg(feature = "std")]
 mod date_time;
 
 #[cfg(feature = "sign-eventstream")]
@@ -31,6 +42,7 @@ pub mod event_stream;
 pub mod http_request;
 
 /// The version of the signing algorithm to use
+#[cfg(feature = "std")]
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 #[non_exhaustive]
 pub enum SignatureVersion {
@@ -40,6 +52,7 @@ pub enum SignatureVersion {
     V4a,
 }
 
+#[cfg(feature = "std")]
 impl fmt::Display for SignatureVersion {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -54,12 +67,14 @@ impl fmt::Display for SignatureVersion {
 /// This is returned by signing functions, and the signed output will be
 /// different based on what is being signed (for example, an event stream
 /// message, or an HTTP request).
+#[cfg(feature = "std")]
 #[derive(Debug)]
 pub struct SigningOutput<T> {
     output: T,
     signature: String,
 }
 
+#[cfg(feature = "std")]
 impl<T> SigningOutput<T> {
     /// Creates a new [`SigningOutput`]
     pub fn new(output: T, signature: String) -> Self {
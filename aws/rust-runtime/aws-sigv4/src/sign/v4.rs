@@ -3,27 +3,48 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::core::HMAC_256;
 use crate::date_time::format_date;
 use aws_smithy_runtime_api::client::identity::Identity;
-use hmac::{digest::FixedOutput, Hmac, Mac};
-use sha2::{Digest, Sha256};
 use std::time::SystemTime;
 
-/// HashedPayload = Lowercase(HexEncode(Hash(requestPayload)))
-#[allow(dead_code)] // Unused when compiling without certain features
-pub(crate) fn sha256_hex_string(bytes: impl AsRef<[u8]>) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(bytes);
-    hex::encode(hasher.finalize_fixed())
+pub(crate) use crate::core::sha256_hex_string;
+
+/// Returns `true` if payloads are hashed with the CPU's SHA-256 instructions.
+///
+/// The instructions are detected at runtime, with the portable implementation used when they are
+/// missing. Enabling the `sha256-force-soft` feature always uses the portable implementation, which
+/// is useful for reproducing results across machines. Either way, the digests are identical.
+pub fn sha256_hardware_accelerated() -> bool {
+    #[cfg(feature = "sha256-force-soft")]
+    {
+        false
+    }
+    #[cfg(all(
+        not(feature = "sha256-force-soft"),
+        any(target_arch = "x86", target_arch = "x86_64")
+    ))]
+    {
+        // The same features `sha2` checks for before using its SHA-NI backend
+        std::arch::is_x86_feature_detected!("sha")
+            && std::arch::is_x86_feature_detected!("sse2")
+            && std::arch::is_x86_feature_detected!("ssse3")
+            && std::arch::is_x86_feature_detected!("sse4.1")
+    }
+    #[cfg(all(not(feature = "sha256-force-soft"), target_arch = "aarch64"))]
+    {
+        std::arch::is_aarch64_feature_detected!("sha2")
+    }
+    #[cfg(all(
+        not(feature = "sha256-force-soft"),
+        not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64"))
+    ))]
+    {
+        false
+    }
 }
 
-/// Calculates a Sigv4 signature
-pub fn calculate_signature(signing_key: impl AsRef<[u8]>, string_to_sign: &[u8]) -> String {
-    let mut mac = Hmac::<Sha256>::new_from_slice(signing_key.as_ref())
-        .expect("HMAC can take key of any size");
-    mac.update(string_to_sign);
-    hex::encode(mac.finalize_fixed())
-}
+pub use crate::core::calculate_signature;
 
 /// Generates a signing key for Sigv4
 pub fn generate_signing_key(
@@ -32,32 +53,7 @@ pub fn generate_signing_key(
     region: &str,
     service: &str,
 ) -> impl AsRef<[u8]> {
-    // kSecret = your secret access key
-    // kDate = HMAC("AWS4" + kSecret, Date)
-    // kRegion = HMAC(kDate, Region)
-    // kService = HMAC(kRegion, Service)
-    // kSigning = HMAC(kService, "aws4_request")
-
-    let secret = format!("AWS4{}", secret);
-    let mut mac =
-        Hmac::<Sha256>::new_from_slice(secret.as_ref()).expect("HMAC can take key of any size");
-    mac.update(format_date(time).as_bytes());
-    let tag = mac.finalize_fixed();
-
-    // sign region
-    let mut mac = Hmac::<Sha256>::new_from_slice(&tag).expect("HMAC can take key of any size");
-    mac.update(region.as_bytes());
-    let tag = mac.finalize_fixed();
-
-    // sign service
-    let mut mac = Hmac::<Sha256>::new_from_slice(&tag).expect("HMAC can take key of any size");
-    mac.update(service.as_bytes());
-    let tag = mac.finalize_fixed();
-
-    // sign request
-    let mut mac = Hmac::<Sha256>::new_from_slice(&tag).expect("HMAC can take key of any size");
-    mac.update("aws4_request".as_bytes());
-    mac.finalize_fixed()
+    crate::core::generate_signing_key(secret, &format_date(time), region, service)
 }
 
 /// Parameters to use when signing.
@@ -80,8 +76,6 @@ pub struct SigningParams<'a, S> {
     pub(crate) settings: S,
 }
 
-const HMAC_256: &str = "AWS4-HMAC-SHA256";
-
 impl<'a, S> SigningParams<'a, S> {
     /// Returns the region that will be used to sign SigV4 requests
     pub fn region(&self) -> &str {
@@ -109,6 +103,7 @@ impl<'a, S: Default> SigningParams<'a, S> {
 /// Builder and error for creating [`SigningParams`]
 pub mod signing_params {
     use super::SigningParams;
+    use aws_smithy_async::time::TimeSource;
     use aws_smithy_runtime_api::client::identity::Identity;
     use std::error::Error;
     use std::fmt;
@@ -167,6 +162,13 @@ pub mod signing_params {
             "Sets additional signing settings (required)"
         );
 
+        /// Sets the time to be used in the signature to the current time of `time_source`
+        ///
+        /// This lets tests control the signing time, and so when a presigned request expires.
+        pub fn time_source(self, time_source: &impl TimeSource) -> Self {
+            self.time(time_source.now())
+        }
+
         /// Builds an instance of [`SigningParams`]. Will yield a [`BuildError`] if
         /// a required argument was not given.
         pub fn build(self) -> Result<SigningParams<'a, S>, BuildError> {
@@ -196,6 +198,7 @@ mod tests {
     use super::{calculate_signature, generate_signing_key, sha256_hex_string};
     use crate::date_time::test_parsers::parse_date_time;
     use crate::http_request::test;
+    use sha2::{Digest, Sha256};
 
     #[test]
     fn test_signature_calculation() {
@@ -216,4 +219,19 @@ mod tests {
         let actual = sha256_hex_string([]);
         assert_eq!(expected, actual);
     }
+
+    // Run with and without the `sha256-force-soft` feature to check both implementations.
+    #[test]
+    fn sign_payload_large() {
+        let payload = vec![b'a'; 1_000_000];
+        let expected = "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0";
+        assert_eq!(expected, sha256_hex_string(&payload));
+
+        // Hashing in chunks that don't line up with SHA-256 blocks must give the same digest
+        let mut hasher = Sha256::new();
+        for chunk in payload.chunks(1000 - 1) {
+            hasher.update(chunk);
+        }
+        assert_eq!(expected, hex::encode(hasher.finalize()));
+    }
 }
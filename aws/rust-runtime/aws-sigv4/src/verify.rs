@@ -0,0 +1,326 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Functions for verifying Sigv4 and Sigv4a signatures on the receiving side of a request.
+//!
+//! These functions only check a signature against a canonical request that has already been
+//! built. Parsing the incoming request, reconstructing its canonical request, and rejecting
+//! requests whose signing time is too far in the past or future are the caller's responsibility.
+
+use crate::date_time::{format_date, format_date_time};
+use crate::sign::v4;
+use aws_credential_types::Credentials;
+#[cfg(feature = "sign-http")]
+use aws_smithy_async::time::TimeSource;
+#[cfg(feature = "sign-http")]
+use std::collections::HashSet;
+#[cfg(feature = "sign-http")]
+use std::time::Duration;
+
+/// Compares two byte slices in constant time.
+///
+/// The time taken depends only on the length of the inputs, not on their contents, so this is
+/// safe to use when comparing a received signature against an expected one.
+pub fn constant_time_equals(a: &[u8], b: &[u8]) -> bool {
+    // The length of a signature isn't secret, so it's fine to return early when they differ.
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+/// Verifies a Sigv4 signature.
+///
+/// The expected signature is recomputed from the `canonical_request` and the signing `params`,
+/// and then compared against `signature` (a lowercase hex string) in constant time. Returns
+/// `false` if the identity in `params` isn't AWS credentials.
+pub fn verify_v4_signature<S>(
+    canonical_request: &str,
+    signature: &str,
+    params: &v4::SigningParams<'_, S>,
+) -> bool {
+    let Some(creds) = params.identity.data::<Credentials>() else {
+        return false;
+    };
+    let string_to_sign = format!(
+        "{}\n{}\n{}/{}/{}/aws4_request\n{}",
+        params.algorithm(),
+        format_date_time(params.time),
+        format_date(params.time),
+        params.region,
+        params.name,
+        v4::sha256_hex_string(canonical_request),
+    );
+    let signing_key = v4::generate_signing_key(
+        creds.secret_access_key(),
+        params.time,
+        params.region,
+        params.name,
+    );
+    let expected = v4::calculate_signature(signing_key, string_to_sign.as_bytes());
+    constant_time_equals(expected.as_bytes(), signature.as_bytes())
+}
+
+/// Verifies a Sigv4a signature.
+///
+/// Unlike Sigv4, a Sigv4a signature can't be recomputed and compared, because ECDSA signatures
+/// are randomized. Instead, the signature is checked with the public key derived from the
+/// credentials in `params`; see [`v4a::verify`](crate::sign::v4a::verify). Returns `false` if the
+/// identity in `params` isn't AWS credentials, or if `signature` isn't a well-formed signature.
+#[cfg(feature = "sigv4a")]
+pub fn verify_v4a_signature<S>(
+    canonical_request: &str,
+    signature: &str,
+    params: &crate::sign::v4a::SigningParams<'_, S>,
+) -> bool {
+    let Some(creds) = params.identity.data::<Credentials>() else {
+        return false;
+    };
+    crate::sign::v4a::verify(
+        canonical_request,
+        signature,
+        creds.access_key_id(),
+        creds.secret_access_key(),
+        params.name,
+        params.time,
+    )
+}
+
+/// Checks the nonce of a presigned request against the nonces that have already been seen.
+///
+/// `query` is the query string of the presigned request, whose `X-Amz-Nonce` param is the nonce
+/// set by [`SigningSettings::with_presign_nonce`](crate::http_request::SigningSettings::with_presign_nonce).
+/// Returns `false` if the query doesn't have a nonce, or if the nonce is already in `seen_nonces`.
+/// Otherwise, the nonce is added to `seen_nonces` so that a replay of the request is rejected, and
+/// `true` is returned.
+///
+/// The nonce is covered by the signature, so this should only be called once the signature has
+/// been verified.
+#[cfg(feature = "sign-http")]
+pub fn verify_presigned_nonce(query: &str, seen_nonces: &mut HashSet<String>) -> bool {
+    let nonce = form_urlencoded::parse(query.as_bytes())
+        .find(|(name, _)| name == "X-Amz-Nonce")
+        .map(|(_, nonce)| nonce);
+    match nonce {
+        Some(nonce) if !nonce.is_empty() => seen_nonces.insert(nonce.into_owned()),
+        _ => false,
+    }
+}
+
+/// Checks that a presigned request hasn't expired yet, according to `time_source`.
+///
+/// `query` is the query string of the presigned request. The request expires `X-Amz-Expires`
+/// seconds after it was signed at `X-Amz-Date`. Returns `false` if it has expired, or if either
+/// param is missing or malformed.
+///
+/// Use the same [`TimeSource`] to sign requests (see
+/// [`Builder::time_source`](crate::sign::v4::signing_params::Builder::time_source)) and to check
+/// them, such as a `ManualTimeSource` in tests, to make the expiry deterministic.
+#[cfg(feature = "sign-http")]
+pub fn verify_presigned_expiry(query: &str, time_source: &impl TimeSource) -> bool {
+    let (mut signed_at, mut expires_in) = (None, None);
+    for (name, value) in form_urlencoded::parse(query.as_bytes()) {
+        match name.as_ref() {
+            "X-Amz-Date" => signed_at = crate::date_time::parse_amz_date_time(&value),
+            "X-Amz-Expires" => expires_in = value.parse().ok().map(Duration::from_secs),
+            _ => {}
+        }
+    }
+    match (signed_at, expires_in) {
+        (Some(signed_at), Some(expires_in)) => signed_at
+            .checked_add(expires_in)
+            .map_or(false, |expires_at| time_source.now() < expires_at),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{constant_time_equals, verify_v4_signature};
+    use crate::sign::v4;
+    use aws_credential_types::Credentials;
+    use aws_smithy_runtime_api::client::identity::Identity;
+    use std::time::{Duration, SystemTime};
+
+    const CANONICAL_REQUEST: &str = "GET\n/\n\nhost:example.amazonaws.com\nx-amz-date:20150830T123600Z\n\nhost;x-amz-date\ne3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+    const SIGNATURE: &str = "5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31";
+
+    fn identity() -> Identity {
+        Credentials::new(
+            "AKIDEXAMPLE",
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            None,
+            None,
+            "test",
+        )
+        .into()
+    }
+
+    fn time() -> SystemTime {
+        // 2015-08-30T12:36:00Z
+        SystemTime::UNIX_EPOCH + Duration::from_secs(1440938160)
+    }
+
+    #[test]
+    fn constant_time_equals_compares_contents() {
+        assert!(constant_time_equals(b"", b""));
+        assert!(constant_time_equals(b"signature", b"signature"));
+        assert!(!constant_time_equals(b"signature", b"signaturf"));
+        assert!(!constant_time_equals(b"signature", b"signatures"));
+        assert!(!constant_time_equals(b"signature", b""));
+    }
+
+    #[test]
+    fn verify_v4() {
+        let identity = identity();
+        let params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region("us-east-1")
+            .name("service")
+            .time(time())
+            .settings(())
+            .build()
+            .unwrap();
+
+        assert!(verify_v4_signature(CANONICAL_REQUEST, SIGNATURE, &params));
+
+        let mut mismatched = SIGNATURE.to_owned();
+        mismatched.replace_range(0..1, "6");
+        assert!(!verify_v4_signature(
+            CANONICAL_REQUEST,
+            &mismatched,
+            &params
+        ));
+        assert!(!verify_v4_signature(
+            CANONICAL_REQUEST,
+            &SIGNATURE[..SIGNATURE.len() - 1],
+            &params
+        ));
+        assert!(!verify_v4_signature(
+            &CANONICAL_REQUEST.replace("GET", "PUT"),
+            SIGNATURE,
+            &params
+        ));
+    }
+
+    #[cfg(feature = "sign-http")]
+    #[test]
+    fn verify_presigned_nonce_rejects_repeats() {
+        use super::verify_presigned_nonce;
+        use std::collections::HashSet;
+
+        let mut seen = HashSet::new();
+        let query = "X-Amz-Date=20150830T123600Z&X-Amz-Nonce=8e1d2f7c&X-Amz-Signature=abc";
+        assert!(verify_presigned_nonce(query, &mut seen));
+        assert!(!verify_presigned_nonce(query, &mut seen));
+        assert!(verify_presigned_nonce(
+            "X-Amz-Nonce=5b0a9e31&X-Amz-Signature=abc",
+            &mut seen
+        ));
+
+        assert!(!verify_presigned_nonce("X-Amz-Signature=abc", &mut seen));
+        assert!(!verify_presigned_nonce(
+            "X-Amz-Nonce=&X-Amz-Signature=abc",
+            &mut seen
+        ));
+        assert_eq!(2, seen.len());
+    }
+
+    #[cfg(feature = "sign-http")]
+    #[test]
+    fn verify_presigned_expiry_with_manual_time() {
+        use super::verify_presigned_expiry;
+        use crate::http_request::{
+            sign, SignableBody, SignableRequest, SignatureLocation, SigningSettings,
+        };
+        use aws_smithy_async::test_util::ManualTimeSource;
+
+        let time_source = ManualTimeSource::new(time());
+        let identity = identity();
+        let settings = SigningSettings::default()
+            .with_signature_location(SignatureLocation::QueryParams)
+            .with_expires_in(Duration::from_secs(60));
+        let params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region("us-east-1")
+            .name("service")
+            .time_source(&time_source)
+            .settings(settings)
+            .build()
+            .unwrap()
+            .into();
+        let request = SignableRequest::new(
+            "GET",
+            "https://example.amazonaws.com/",
+            std::iter::empty(),
+            SignableBody::Bytes(&[]),
+        )
+        .unwrap();
+        let (instructions, _signature) = sign(request, &params).unwrap().into_parts();
+        let query = instructions
+            .params()
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("&");
+        assert!(query.contains("X-Amz-Date=20150830T123600Z"), "{query}");
+
+        assert!(verify_presigned_expiry(&query, &time_source));
+        time_source.advance(Duration::from_secs(59));
+        assert!(verify_presigned_expiry(&query, &time_source));
+        time_source.advance(Duration::from_secs(1));
+        assert!(!verify_presigned_expiry(&query, &time_source));
+
+        let time_source = ManualTimeSource::new(time());
+        assert!(!verify_presigned_expiry(
+            "X-Amz-Date=20150830T123600Z",
+            &time_source
+        ));
+        assert!(!verify_presigned_expiry(
+            "X-Amz-Date=yesterday&X-Amz-Expires=60",
+            &time_source
+        ));
+    }
+
+    #[cfg(feature = "sigv4a")]
+    #[test]
+    fn verify_v4a() {
+        use super::verify_v4a_signature;
+        use crate::date_time::format_date_time;
+        use crate::sign::v4a;
+
+        let identity = identity();
+        let params = v4a::SigningParams::builder()
+            .identity(&identity)
+            .region_set(v4a::RegionSet::parse("us-east-1").unwrap())
+            .name("service")
+            .time(time())
+            .settings(())
+            .build()
+            .unwrap();
+
+        let string_to_sign = format!(
+            "AWS4-ECDSA-P256-SHA256\n{}\n20150830/service/aws4_request\n{}",
+            format_date_time(time()),
+            v4::sha256_hex_string(CANONICAL_REQUEST)
+        );
+        let signing_key =
+            v4a::generate_signing_key("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY");
+        let signature = v4a::calculate_signature(signing_key, string_to_sign.as_bytes());
+
+        assert!(verify_v4a_signature(CANONICAL_REQUEST, &signature, &params));
+        assert!(!verify_v4a_signature(
+            &CANONICAL_REQUEST.replace("GET", "PUT"),
+            &signature,
+            &params
+        ));
+        assert!(!verify_v4a_signature(
+            CANONICAL_REQUEST,
+            &signature[..signature.len() - 2],
+            &params
+        ));
+        assert!(!verify_v4a_signature(CANONICAL_REQUEST, "not hex", &params));
+    }
+}
@@ -5,14 +5,36 @@
 
 //! New-type for a configurable app name.
 
+use crate::build_metadata::OsFamily;
+use crate::os_shim_internal::Env;
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
 use std::borrow::Cow;
+use std::env::VarError;
 use std::error::Error;
 use std::fmt;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 static APP_NAME_LEN_RECOMMENDATION_WARN_EMITTED: AtomicBool = AtomicBool::new(false);
 
+/// The recommended maximum length of an app name.
+const APP_NAME_LEN_RECOMMENDATION: usize = 50;
+
+/// The environment variable read by [`AppName::from_env`].
+const APP_NAME_ENV_VAR: &str = "AWS_SDK_UA_APP_ID";
+
+/// The separator used by [`AppName::with_suffix`].
+const SUFFIX_SEPARATOR: char = '-';
+
+/// Returns `true` if `c` may appear in an app name, or in any other user-agent token.
+fn valid_character(c: char) -> bool {
+    match c {
+        _ if c.is_ascii_alphanumeric() => true,
+        '!' | '#' | '$' | '%' | '&' | '\'' | '*' | '+' | '-' | '.' | '^' | '_' | '`' | '|'
+        | '~' => true,
+        _ => false,
+    }
+}
+
 /// App name that can be configured with an AWS SDK client to become part of the user agent string.
 ///
 /// This name is used to identify the application in the user agent that gets sent along with requests.
@@ -43,6 +65,21 @@ impl Storable for AppName {
     type Storer = StoreReplace<AppName>;
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for AppName {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_ref())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for AppName {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let app_name = String::deserialize(deserializer)?;
+        AppName::new(app_name).map_err(serde::de::Error::custom)
+    }
+}
+
 impl AppName {
     /// Creates a new app name.
     ///
@@ -52,20 +89,12 @@ impl AppName {
         let app_name = app_name.into();
 
         if app_name.is_empty() {
-            return Err(InvalidAppName);
-        }
-        fn valid_character(c: char) -> bool {
-            match c {
-                _ if c.is_ascii_alphanumeric() => true,
-                '!' | '#' | '$' | '%' | '&' | '\'' | '*' | '+' | '-' | '.' | '^' | '_' | '`'
-                | '|' | '~' => true,
-                _ => false,
-            }
+            return Err(InvalidAppName::invalid_characters());
         }
         if !app_name.chars().all(valid_character) {
-            return Err(InvalidAppName);
+            return Err(InvalidAppName::invalid_characters());
         }
-        if app_name.len() > 50 {
+        if app_name.len() > APP_NAME_LEN_RECOMMENDATION {
             if let Ok(false) = APP_NAME_LEN_RECOMMENDATION_WARN_EMITTED.compare_exchange(
                 false,
                 true,
@@ -80,32 +109,163 @@ impl AppName {
         }
         Ok(Self(app_name))
     }
+
+    /// Loads the app name from the `AWS_SDK_UA_APP_ID` environment variable.
+    ///
+    /// Returns `None` if the variable isn't set, and an error if its value isn't a valid app name
+    /// according to [`AppName::new`]. Reading through an [`Env`] allows using a fake environment
+    /// in tests.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use aws_types::app_name::AppName;
+    /// use aws_types::os_shim_internal::Env;
+    ///
+    /// let env = Env::from_slice(&[("AWS_SDK_UA_APP_ID", "my-app")]);
+    /// assert_eq!("my-app", AppName::from_env(&env).unwrap().unwrap().as_ref());
+    /// assert_eq!(None, AppName::from_env(&Env::from_slice(&[])).unwrap());
+    /// ```
+    pub fn from_env(env: &Env) -> Result<Option<AppName>, InvalidAppName> {
+        match env.get(APP_NAME_ENV_VAR) {
+            Ok(app_name) => AppName::new(app_name).map(Some),
+            Err(VarError::NotPresent) => Ok(None),
+            Err(VarError::NotUnicode(_)) => Err(InvalidAppName::invalid_characters()),
+        }
+    }
+
+    /// Creates a new app name by appending `suffix` to this one, separated by a `-`.
+    ///
+    /// This is useful for frameworks that wrap the SDK and want to identify themselves
+    /// alongside the app name they were given. The combined name must meet the same character
+    /// requirements as [`AppName::new`], so the suffix can't contain characters such as `/` or
+    /// spaces. Unlike [`AppName::new`], which only warns about long names, this returns an error
+    /// if the combined name is longer than the recommended 50 characters.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use aws_types::app_name::AppName;
+    ///
+    /// let app_name = AppName::new("my-app").unwrap();
+    /// let app_name = app_name.with_suffix("myframework_1.2").unwrap();
+    /// assert_eq!("my-app-myframework_1.2", app_name.as_ref());
+    /// ```
+    pub fn with_suffix(&self, suffix: &str) -> Result<AppName, InvalidAppName> {
+        let combined = format!("{}{SUFFIX_SEPARATOR}{suffix}", self.0);
+        if combined.len() > APP_NAME_LEN_RECOMMENDATION {
+            return Err(InvalidAppName::too_long());
+        }
+        AppName::new(combined)
+    }
+
+    /// Returns a user-agent fragment identifying this app and the platform it runs on.
+    ///
+    /// The fragment has the form `app/{app_name} md/os#{os_family} md/arch#{arch}`, where each
+    /// space-separated part is a valid user-agent token. Returns an error if `arch` contains
+    /// characters that aren't allowed in a user-agent token, or is empty.
+    ///
+    /// Use [`BUILD_METADATA`](crate::build_metadata::BUILD_METADATA) and
+    /// [`TARGET_ARCH`](crate::build_metadata::TARGET_ARCH) to describe the platform the SDK was
+    /// compiled for.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use aws_types::app_name::AppName;
+    /// use aws_types::build_metadata::{OsFamily, BUILD_METADATA, TARGET_ARCH};
+    ///
+    /// let app_name = AppName::new("my-app").unwrap();
+    /// assert_eq!(
+    ///     "app/my-app md/os#linux md/arch#x86_64",
+    ///     app_name.user_agent_fragment(&OsFamily::Linux, "x86_64").unwrap()
+    /// );
+    ///
+    /// // The platform the SDK was compiled for.
+    /// let fragment = app_name
+    ///     .user_agent_fragment(&BUILD_METADATA.os_family, TARGET_ARCH)
+    ///     .unwrap();
+    /// ```
+    pub fn user_agent_fragment(
+        &self,
+        os_family: &OsFamily,
+        arch: &str,
+    ) -> Result<String, InvalidAppName> {
+        if arch.is_empty() || !arch.chars().all(valid_character) {
+            return Err(InvalidAppName::invalid_arch());
+        }
+        Ok(format!("app/{} md/os#{os_family} md/arch#{arch}", self.0))
+    }
 }
 
-/// Error for when an app name doesn't meet character requirements.
+/// Error for when an app name, or the architecture passed to [`AppName::user_agent_fragment`],
+/// doesn't meet character requirements.
 ///
 /// See [`AppName`] for details on these requirements.
 #[derive(Debug)]
 #[non_exhaustive]
-pub struct InvalidAppName;
+pub struct InvalidAppName {
+    kind: InvalidAppNameKind,
+}
+
+#[derive(Debug)]
+enum InvalidAppNameKind {
+    InvalidCharacters,
+    TooLong,
+    InvalidArch,
+}
+
+impl InvalidAppName {
+    fn invalid_characters() -> Self {
+        Self {
+            kind: InvalidAppNameKind::InvalidCharacters,
+        }
+    }
+
+    fn too_long() -> Self {
+        Self {
+            kind: InvalidAppNameKind::TooLong,
+        }
+    }
+
+    fn invalid_arch() -> Self {
+        Self {
+            kind: InvalidAppNameKind::InvalidArch,
+        }
+    }
+}
 
 impl Error for InvalidAppName {}
 
 impl fmt::Display for InvalidAppName {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "The app name can only have alphanumeric characters, or any of \
-             '!' |  '#' |  '$' |  '%' |  '&' |  '\\'' |  '*' |  '+' |  '-' | \
-             '.' |  '^' |  '_' |  '`' |  '|' |  '~'"
-        )
+        match self.kind {
+            InvalidAppNameKind::InvalidCharacters => write!(
+                f,
+                "The app name can only have alphanumeric characters, or any of \
+                 '!' |  '#' |  '$' |  '%' |  '&' |  '\\'' |  '*' |  '+' |  '-' | \
+                 '.' |  '^' |  '_' |  '`' |  '|' |  '~'"
+            ),
+            InvalidAppNameKind::TooLong => write!(
+                f,
+                "The app name can have no more than {APP_NAME_LEN_RECOMMENDATION} characters \
+                 after adding a suffix"
+            ),
+            InvalidAppNameKind::InvalidArch => write!(
+                f,
+                "The architecture in a user-agent fragment can't be empty, and can only have \
+                 the same characters as an app name"
+            ),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::AppName;
+    use super::{valid_character, AppName};
     use crate::app_name::APP_NAME_LEN_RECOMMENDATION_WARN_EMITTED;
+    use crate::build_metadata::{OsFamily, BUILD_METADATA, TARGET_ARCH};
+    use crate::os_shim_internal::Env;
     use std::sync::atomic::Ordering;
 
     #[test]
@@ -116,6 +276,102 @@ mod tests {
         assert!(AppName::new("").is_err());
     }
 
+    #[test]
+    fn from_env() {
+        let env = Env::from_slice(&[("AWS_SDK_UA_APP_ID", "my-app")]);
+        assert_eq!(
+            Some(AppName::new("my-app").unwrap()),
+            AppName::from_env(&env).unwrap()
+        );
+
+        let env = Env::from_slice(&[("AWS_REGION", "us-east-1")]);
+        assert_eq!(None, AppName::from_env(&env).unwrap());
+
+        for invalid in ["my app", "", "🚀"] {
+            let env = Env::from_slice(&[("AWS_SDK_UA_APP_ID", invalid)]);
+            assert!(AppName::from_env(&env).is_err(), "`{invalid}`");
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let app_name = AppName::new("my-app").unwrap();
+        let json = serde_json::to_string(&app_name).unwrap();
+        assert_eq!("\"my-app\"", json);
+        assert_eq!(app_name, serde_json::from_str::<AppName>(&json).unwrap());
+        // Deserialization runs the same validation as `AppName::new`
+        assert!(serde_json::from_str::<AppName>("\"foo bar\"").is_err());
+    }
+
+    #[test]
+    fn with_suffix() {
+        let app_name = AppName::new("my-app").unwrap();
+        assert_eq!(
+            "my-app-myframework_1.2",
+            app_name.with_suffix("myframework_1.2").unwrap().as_ref()
+        );
+        // The original app name is unchanged
+        assert_eq!("my-app", app_name.as_ref());
+    }
+
+    #[test]
+    fn with_suffix_validation() {
+        let app_name = AppName::new("my-app").unwrap();
+        assert!(app_name.with_suffix("myframework/1.2").is_err());
+        assert!(app_name.with_suffix("my framework").is_err());
+
+        // 6 + 1 + 43 = 50 characters is allowed, but 51 isn't
+        assert!(app_name.with_suffix(&"a".repeat(43)).is_ok());
+        let err = app_name.with_suffix(&"a".repeat(44)).unwrap_err();
+        assert!(err.to_string().contains("no more than 50 characters"));
+    }
+
+    #[test]
+    fn user_agent_fragment() {
+        let app_name = AppName::new("my-app").unwrap();
+        assert_eq!(
+            "app/my-app md/os#linux md/arch#x86_64",
+            app_name
+                .user_agent_fragment(&OsFamily::Linux, "x86_64")
+                .unwrap()
+        );
+        assert_eq!(
+            "app/my-app md/os#macos md/arch#aarch64",
+            app_name
+                .user_agent_fragment(&OsFamily::Macos, "aarch64")
+                .unwrap()
+        );
+        assert_eq!(
+            "app/my-app md/os#other md/arch#wasm32",
+            app_name
+                .user_agent_fragment(&OsFamily::Other, "wasm32")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn user_agent_fragment_is_made_of_tokens() {
+        let app_name = AppName::new("my-app").unwrap();
+        for arch in ["", "x86 64", "x86/64"] {
+            let err = app_name
+                .user_agent_fragment(&OsFamily::Linux, arch)
+                .unwrap_err();
+            assert!(
+                err.to_string().starts_with("The architecture"),
+                "`{arch}`: {err}"
+            );
+        }
+
+        let fragment = app_name
+            .user_agent_fragment(&BUILD_METADATA.os_family, TARGET_ARCH)
+            .unwrap();
+        for part in fragment.split(' ') {
+            let (_prefix, value) = part.split_once('/').unwrap();
+            assert!(!value.is_empty() && value.chars().all(valid_character));
+        }
+    }
+
     #[tracing_test::traced_test]
     #[test]
     fn log_warn_once() {
@@ -5,19 +5,93 @@
 
 //! Metadata type used by all service SDKs to provide information about the environment the SDK was compiled for.
 
+use std::error::Error;
+use std::fmt;
+
 include!(concat!(env!("OUT_DIR"), "/build_env.rs"));
 
 /// Metadata about the environment the SDK was compiled for.
-#[derive(Debug)]
-pub struct BuildMetadata {
+///
+/// The [`Display`](fmt::Display) implementation produces the canonical form,
+/// `aws-types/{core_pkg_version} lang/rust/{rust_version} os/{os_family}`, which can be parsed
+/// back with [`BuildMetadata::parse`]. Parsed metadata borrows its versions from the string it
+/// was parsed from, while [`BUILD_METADATA`] is `'static`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildMetadata<'a> {
     /// Which version of Rust was used to compile the SDK.
-    pub rust_version: &'static str,
+    pub rust_version: &'a str,
     /// The version number of the `aws-types` crate compiled with the SDK.
-    pub core_pkg_version: &'static str,
+    pub core_pkg_version: &'a str,
     /// The OS the SDK was compiled for.
     pub os_family: OsFamily,
 }
 
+impl<'a> BuildMetadata<'a> {
+    /// Parses build metadata from its canonical form.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use aws_types::build_metadata::{BuildMetadata, OsFamily};
+    ///
+    /// let metadata = BuildMetadata::parse("aws-types/1.3.3 lang/rust/1.78.0 os/linux").unwrap();
+    /// assert_eq!("1.3.3", metadata.core_pkg_version);
+    /// assert_eq!("1.78.0", metadata.rust_version);
+    /// assert_eq!(OsFamily::Linux, metadata.os_family);
+    /// ```
+    pub fn parse(metadata: &'a str) -> Result<Self, InvalidBuildMetadata> {
+        let mut parts = metadata.split(' ');
+        let mut next = |prefix: &'static str| {
+            parts
+                .next()
+                .and_then(|part| part.strip_prefix(prefix))
+                .filter(|value| !value.is_empty())
+                .ok_or(InvalidBuildMetadata::new(prefix))
+        };
+        let core_pkg_version = next("aws-types/")?;
+        let rust_version = next("lang/rust/")?;
+        let os_family = next("os/")?.into();
+        if parts.next().is_some() {
+            return Err(InvalidBuildMetadata::new("end of input"));
+        }
+        Ok(Self {
+            rust_version,
+            core_pkg_version,
+            os_family,
+        })
+    }
+}
+
+impl fmt::Display for BuildMetadata<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "aws-types/{} lang/rust/{} os/{}",
+            self.core_pkg_version, self.rust_version, self.os_family
+        )
+    }
+}
+
+/// Error returned when [`BuildMetadata::parse`] is given malformed metadata.
+#[derive(Debug)]
+pub struct InvalidBuildMetadata {
+    expected: &'static str,
+}
+
+impl InvalidBuildMetadata {
+    fn new(expected: &'static str) -> Self {
+        Self { expected }
+    }
+}
+
+impl fmt::Display for InvalidBuildMetadata {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid build metadata: expected `{}`", self.expected)
+    }
+}
+
+impl Error for InvalidBuildMetadata {}
+
 /// Operating system family that the SDK can be compiled for
 #[derive(PartialEq, Eq, Clone, Debug)]
 pub enum OsFamily {
@@ -64,6 +138,19 @@ impl OsFamily {
     }
 }
 
+impl fmt::Display for OsFamily {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            OsFamily::Windows => "windows",
+            OsFamily::Linux => "linux",
+            OsFamily::Macos => "macos",
+            OsFamily::Android => "android",
+            OsFamily::Ios => "ios",
+            OsFamily::Other => "other",
+        })
+    }
+}
+
 impl From<&str> for OsFamily {
     fn from(s: &str) -> Self {
         match s {
@@ -78,15 +165,20 @@ impl From<&str> for OsFamily {
 }
 
 /// Constant build metadata for this crate.
-pub const BUILD_METADATA: BuildMetadata = BuildMetadata {
+pub const BUILD_METADATA: BuildMetadata<'static> = BuildMetadata {
     rust_version: RUST_VERSION,
     core_pkg_version: env!("CARGO_PKG_VERSION"),
     os_family: OsFamily::from_env(),
 };
 
+/// The CPU architecture this crate was compiled for, such as `x86_64` or `aarch64`.
+///
+/// This is the value of [`std::env::consts::ARCH`].
+pub const TARGET_ARCH: &str = std::env::consts::ARCH;
+
 #[cfg(test)]
 mod test {
-    use crate::build_metadata::{OsFamily, BUILD_METADATA};
+    use crate::build_metadata::{BuildMetadata, OsFamily, BUILD_METADATA};
 
     #[test]
     fn valid_build_metadata() {
@@ -104,4 +196,42 @@ mod test {
             assert_eq!(meta.os_family, OsFamily::Macos);
         }
     }
+
+    #[test]
+    fn round_trip_embedded_metadata() {
+        let canonical = BUILD_METADATA.to_string();
+        assert!(canonical.starts_with(&format!("aws-types/{}", env!("CARGO_PKG_VERSION"))));
+        assert_eq!(BUILD_METADATA, BuildMetadata::parse(&canonical).unwrap());
+    }
+
+    #[test]
+    fn os_family_round_trip() {
+        for os_family in [
+            OsFamily::Windows,
+            OsFamily::Linux,
+            OsFamily::Macos,
+            OsFamily::Android,
+            OsFamily::Ios,
+            OsFamily::Other,
+        ] {
+            assert_eq!(os_family, OsFamily::from(os_family.to_string().as_str()));
+        }
+    }
+
+    #[test]
+    fn invalid_build_metadata() {
+        for metadata in [
+            "",
+            "aws-types/1.3.3",
+            "aws-types/1.3.3 lang/rust/1.78.0",
+            "aws-types/ lang/rust/1.78.0 os/linux",
+            "lang/rust/1.78.0 aws-types/1.3.3 os/linux",
+            "aws-types/1.3.3 lang/rust/1.78.0 os/linux extra",
+        ] {
+            assert!(
+                BuildMetadata::parse(metadata).is_err(),
+                "`{metadata}` should be rejected"
+            );
+        }
+    }
 }
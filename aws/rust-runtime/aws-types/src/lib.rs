@@ -30,29 +30,169 @@ pub mod service_config;
 pub use sdk_config::SdkConfig;
 
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use once_cell::sync::Lazy;
 use std::borrow::Cow;
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+/// The smallest pool of interned signing names that gets pruned
+const MIN_INTERNED_PRUNE_LEN: usize = 64;
+
+#[derive(Debug)]
+struct InternedNames {
+    names: HashSet<Arc<str>>,
+    /// Unused names are pruned when adding a name to a pool this large
+    prune_at: usize,
+}
+
+static INTERNED_NAMES: Lazy<RwLock<InternedNames>> = Lazy::new(|| {
+    RwLock::new(InternedNames {
+        names: HashSet::new(),
+        prune_at: MIN_INTERNED_PRUNE_LEN,
+    })
+});
 
 /// The name of the service used to sign this request
 ///
 /// Generally, user code should never interact with `SigningName` directly
-#[derive(Clone, Debug, PartialEq, Eq)]
-pub struct SigningName(Cow<'static, str>);
+#[derive(Clone)]
+pub struct SigningName(Inner);
+
+#[derive(Clone)]
+enum Inner {
+    Cow(Cow<'static, str>),
+    Interned(Arc<str>),
+}
+
 impl AsRef<str> for SigningName {
     fn as_ref(&self) -> &str {
-        &self.0
+        match &self.0 {
+            Inner::Cow(name) => name,
+            Inner::Interned(name) => name,
+        }
     }
 }
 
+impl fmt::Debug for SigningName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SigningName").field(&self.as_ref()).finish()
+    }
+}
+
+impl PartialEq for SigningName {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_ref() == other.as_ref()
+    }
+}
+
+impl Eq for SigningName {}
+
 impl SigningName {
     /// Creates a `SigningName` from a static str.
     pub fn from_static(name: &'static str) -> Self {
-        SigningName(Cow::Borrowed(name))
+        SigningName(Inner::Cow(Cow::Borrowed(name)))
+    }
+
+    /// Creates a `SigningName`, checking that it could produce a valid signature.
+    ///
+    /// Returns an error if `name` is empty or contains non-ASCII characters. The other
+    /// constructors don't check the name, and are kept for names that are known to be valid.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use aws_types::SigningName;
+    ///
+    /// assert_eq!(SigningName::try_new("s3").unwrap(), "s3");
+    /// assert!(SigningName::try_new("").is_err());
+    /// ```
+    pub fn try_new(name: impl Into<Cow<'static, str>>) -> Result<Self, InvalidSigningName> {
+        let name = name.into();
+        if name.is_empty() {
+            return Err(InvalidSigningName {
+                kind: InvalidSigningNameKind::Empty,
+            });
+        }
+        if !name.is_ascii() {
+            return Err(InvalidSigningName {
+                kind: InvalidSigningNameKind::NonAscii,
+            });
+        }
+        Ok(SigningName::from_cow(name))
+    }
+
+    /// Creates a `SigningName` from a `Cow`, keeping it borrowed if it already is.
+    pub fn from_cow(name: Cow<'static, str>) -> Self {
+        SigningName(Inner::Cow(name))
+    }
+
+    /// Returns the name as a `Cow` that borrows from this `SigningName`, so it never allocates.
+    ///
+    /// To keep the name beyond this borrow, clone the `SigningName` instead. Cloning never copies a
+    /// static or interned name.
+    pub fn as_cow(&self) -> Cow<'_, str> {
+        Cow::Borrowed(self.as_ref())
+    }
+
+    /// Returns true if the name is a static str, and so is never allocated or copied.
+    pub fn is_static(&self) -> bool {
+        matches!(self.0, Inner::Cow(Cow::Borrowed(_)))
+    }
+
+    /// Creates a `SigningName` from a process-wide pool of names, adding `name` to it if needed.
+    ///
+    /// Only the first call for a given name allocates. Every `SigningName` interned for that name
+    /// shares the same `Arc<str>`, so cloning one never copies the name.
+    ///
+    /// Looking up a name that's already in the pool only takes a read lock. Names that no
+    /// `SigningName` uses anymore are dropped from the pool when it grows past a threshold, which
+    /// is at least 64 names and doubles with the number of names still in use.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use aws_types::SigningName;
+    ///
+    /// let first = SigningName::interned(&format!("s3-{}", "outposts"));
+    /// let second = SigningName::interned("s3-outposts");
+    /// assert_eq!(first.as_ref().as_ptr(), second.as_ref().as_ptr());
+    /// assert_eq!(first, "s3-outposts");
+    /// ```
+    pub fn interned(name: &str) -> Self {
+        let interned = INTERNED_NAMES
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .names
+            .get(name)
+            .cloned();
+        if let Some(interned) = interned {
+            return SigningName(Inner::Interned(interned));
+        }
+
+        let mut pool = INTERNED_NAMES
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // Another thread may have added the name since the read lock was released
+        if let Some(interned) = pool.names.get(name) {
+            return SigningName(Inner::Interned(interned.clone()));
+        }
+        if pool.names.len() >= pool.prune_at {
+            // Drop the names that only the pool still holds
+            pool.names
+                .retain(|interned| Arc::strong_count(interned) > 1);
+            pool.prune_at = MIN_INTERNED_PRUNE_LEN.max(pool.names.len() * 2);
+        }
+        let interned: Arc<str> = name.into();
+        pool.names.insert(interned.clone());
+        SigningName(Inner::Interned(interned))
     }
 }
 
 impl From<String> for SigningName {
     fn from(name: String) -> Self {
-        SigningName(Cow::Owned(name))
+        SigningName::from_cow(Cow::Owned(name))
     }
 }
 
@@ -62,6 +202,169 @@ impl From<&'static str> for SigningName {
     }
 }
 
+impl fmt::Display for SigningName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_ref())
+    }
+}
+
+impl PartialEq<str> for SigningName {
+    fn eq(&self, other: &str) -> bool {
+        self.as_ref() == other
+    }
+}
+
+impl PartialEq<&str> for SigningName {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_ref() == *other
+    }
+}
+
 impl Storable for SigningName {
     type Storer = StoreReplace<Self>;
 }
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for SigningName {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_ref())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SigningName {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(SigningName::from)
+    }
+}
+
+/// Error for when a signing name passed to [`SigningName::try_new`] would produce invalid signatures.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct InvalidSigningName {
+    kind: InvalidSigningNameKind,
+}
+
+#[derive(Debug)]
+enum InvalidSigningNameKind {
+    Empty,
+    NonAscii,
+}
+
+impl Error for InvalidSigningName {}
+
+impl fmt::Display for InvalidSigningName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            InvalidSigningNameKind::Empty => write!(f, "The signing name can't be empty"),
+            InvalidSigningNameKind::NonAscii => {
+                write!(f, "The signing name can only have ASCII characters")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SigningName;
+    use std::borrow::Cow;
+
+    #[test]
+    fn borrowed_signing_name_stays_borrowed() {
+        let name = SigningName::from_cow(Cow::Borrowed("s3"));
+        assert!(name.is_static());
+        assert!(matches!(name.as_cow(), Cow::Borrowed("s3")));
+        assert!(SigningName::from_static("s3").is_static());
+        assert!(name.clone().is_static());
+
+        let name = SigningName::from_cow(Cow::Owned("s3".to_owned()));
+        assert!(!name.is_static());
+        assert!(!SigningName::from("s3".to_owned()).is_static());
+        assert!(
+            matches!(name.as_cow(), Cow::Borrowed(borrowed) if borrowed.as_ptr() == name.as_ref().as_ptr())
+        );
+        assert_eq!(SigningName::from_static("s3"), name);
+    }
+
+    #[test]
+    fn interned_signing_names_share_storage() {
+        let first = SigningName::interned(&String::from("interned-service"));
+        let second = SigningName::interned(&String::from("interned-service"));
+        assert_eq!(first, second);
+        assert!(!first.is_static());
+        assert_eq!(first.as_ref().as_ptr(), second.as_ref().as_ptr());
+        assert_eq!(first.as_ref().as_ptr(), first.clone().as_ref().as_ptr());
+        assert_eq!("interned-service", first.as_cow());
+        assert!(
+            matches!(first.as_cow(), Cow::Borrowed(name) if name.as_ptr() == first.as_ref().as_ptr())
+        );
+
+        let other = SigningName::interned("other-interned-service");
+        assert_ne!(first, other);
+        assert_ne!(first.as_ref().as_ptr(), other.as_ref().as_ptr());
+        assert_eq!(SigningName::from_static("interned-service"), first);
+    }
+
+    #[test]
+    fn unused_interned_signing_names_are_dropped() {
+        let name = SigningName::interned("dropped-interned-service");
+        let weak = match &name.0 {
+            super::Inner::Interned(name) => std::sync::Arc::downgrade(name),
+            super::Inner::Cow(_) => unreachable!("interned names are shared"),
+        };
+        drop(name);
+        // The name stays in the pool until the pool grows large enough to be pruned
+        for i in 0..super::MIN_INTERNED_PRUNE_LEN {
+            let _other = SigningName::interned(&format!("pruning-interned-service-{i}"));
+        }
+        assert!(weak.upgrade().is_none());
+    }
+
+    #[test]
+    fn try_new_validates_signing_name() {
+        let name = SigningName::try_new("s3-outposts").unwrap();
+        assert_eq!(name, "s3-outposts");
+        assert!(name.is_static());
+        assert!(!SigningName::try_new("execute-api".to_owned())
+            .unwrap()
+            .is_static());
+
+        let err = SigningName::try_new("").unwrap_err();
+        assert_eq!("The signing name can't be empty", err.to_string());
+        let err = SigningName::try_new("sérvice").unwrap_err();
+        assert_eq!(
+            "The signing name can only have ASCII characters",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn signing_name_display() {
+        assert_eq!("s3", SigningName::from_static("s3").to_string());
+        assert_eq!("s3", format!("{}", SigningName::from("s3".to_owned())));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn signing_name_serde_round_trip() {
+        let name = SigningName::from_static("s3");
+        let json = serde_json::to_string(&name).unwrap();
+        assert_eq!("\"s3\"", json);
+        assert_eq!(name, serde_json::from_str::<SigningName>(&json).unwrap());
+    }
+
+    #[test]
+    fn signing_name_eq_str() {
+        let borrowed = SigningName::from_static("s3");
+        let owned = SigningName::from("s3".to_owned());
+
+        assert_eq!(borrowed, "s3");
+        assert_eq!(owned, "s3");
+        assert!(borrowed == *"s3");
+        assert!(owned == *"s3");
+        assert_ne!(borrowed, "s3-outposts");
+        assert_ne!(owned, "s3-outposts");
+        // Owned and borrowed names with the same value are still equal
+        assert_eq!(borrowed, owned);
+    }
+}
@@ -7,7 +7,7 @@
 //! - Reading environment variables
 //! - Reading from the file system
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::env::VarError;
 use std::ffi::OsString;
 use std::fmt::Debug;
@@ -52,7 +52,10 @@ impl Fs {
 
     /// Create `Fs` from a map of `OsString` to `Vec<u8>`.
     pub fn from_raw_map(fs: HashMap<OsString, Vec<u8>>) -> Self {
-        Fs(fs::Inner::Fake(Arc::new(Fake::MapFs(Mutex::new(fs)))))
+        Fs(fs::Inner::Fake(Arc::new(Fake::MapFs {
+            files: Mutex::new(fs),
+            denied: Mutex::new(HashSet::new()),
+        })))
     }
 
     /// Create `Fs` from a map of `String` to `Vec<u8>`.
@@ -119,6 +122,42 @@ impl Fs {
         Self::from_map(fs)
     }
 
+    /// Simulate a file that the current user doesn't have permission to access.
+    ///
+    /// Reads and writes of `path` will fail with [`PermissionDenied`](std::io::ErrorKind::PermissionDenied),
+    /// whether or not the file exists. Since clones of an `Fs` share their files, access is denied
+    /// through every clone.
+    ///
+    /// This is only supported by in-memory file systems created with [`Fs::from_map`],
+    /// [`Fs::from_raw_map`], [`Fs::from_slice`], or from a `HashMap<PathBuf, Vec<u8>>`. For any
+    /// other file system, an [`Unsupported`](std::io::ErrorKind::Unsupported) error is returned.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # async fn example() {
+    /// use aws_types::os_shim_internal::Fs;
+    /// let mock_fs = Fs::from_slice(&[("/home/.aws/credentials", "[default]")]);
+    /// mock_fs.deny_access("/home/.aws/credentials").unwrap();
+    /// let err = mock_fs.read_to_end("/home/.aws/credentials").await.unwrap_err();
+    /// assert_eq!(std::io::ErrorKind::PermissionDenied, err.kind());
+    /// # }
+    /// ```
+    pub fn deny_access(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        match &self.0 {
+            fs::Inner::Fake(fake) => match fake.as_ref() {
+                Fake::MapFs { denied, .. } => {
+                    denied
+                        .lock()
+                        .unwrap()
+                        .insert(path.as_ref().as_os_str().into());
+                    Ok(())
+                }
+                Fake::NamespacedFs { .. } => Err(std::io::ErrorKind::Unsupported.into()),
+            },
+            fs::Inner::Real => Err(std::io::ErrorKind::Unsupported.into()),
+        }
+    }
+
     /// Read the entire contents of a file
     ///
     /// _Note: This function is currently `async` primarily for forward compatibility. Currently,
@@ -131,12 +170,17 @@ impl Fs {
             // TODO(https://github.com/awslabs/aws-sdk-rust/issues/867): Use async IO below
             Inner::Real => std::fs::read(path),
             Inner::Fake(fake) => match fake.as_ref() {
-                Fake::MapFs(fs) => fs
-                    .lock()
-                    .unwrap()
-                    .get(path.as_os_str())
-                    .cloned()
-                    .ok_or_else(|| std::io::ErrorKind::NotFound.into()),
+                Fake::MapFs { files, denied } => {
+                    if denied.lock().unwrap().contains(path.as_os_str()) {
+                        return Err(std::io::ErrorKind::PermissionDenied.into());
+                    }
+                    files
+                        .lock()
+                        .unwrap()
+                        .get(path.as_os_str())
+                        .cloned()
+                        .ok_or_else(|| std::io::ErrorKind::NotFound.into())
+                }
                 Fake::NamespacedFs {
                     real_path,
                     namespaced_to,
@@ -165,8 +209,12 @@ impl Fs {
                 std::fs::write(path, contents)?;
             }
             Inner::Fake(fake) => match fake.as_ref() {
-                Fake::MapFs(fs) => {
-                    fs.lock()
+                Fake::MapFs { files, denied } => {
+                    if denied.lock().unwrap().contains(path.as_ref().as_os_str()) {
+                        return Err(std::io::ErrorKind::PermissionDenied.into());
+                    }
+                    files
+                        .lock()
                         .unwrap()
                         .insert(path.as_ref().as_os_str().into(), contents.as_ref().to_vec());
                 }
@@ -186,8 +234,18 @@ impl Fs {
     }
 }
 
+impl From<HashMap<PathBuf, Vec<u8>>> for Fs {
+    fn from(files: HashMap<PathBuf, Vec<u8>>) -> Self {
+        let fs = files
+            .into_iter()
+            .map(|(k, v)| (k.into_os_string(), v))
+            .collect();
+        Self::from_raw_map(fs)
+    }
+}
+
 mod fs {
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
     use std::ffi::OsString;
     use std::path::PathBuf;
     use std::sync::{Arc, Mutex};
@@ -200,7 +258,10 @@ mod fs {
 
     #[derive(Debug)]
     pub(super) enum Fake {
-        MapFs(Mutex<HashMap<OsString, Vec<u8>>>),
+        MapFs {
+            files: Mutex<HashMap<OsString, Vec<u8>>>,
+            denied: Mutex<HashSet<OsString>>,
+        },
         NamespacedFs {
             real_path: PathBuf,
             namespaced_to: PathBuf,
@@ -217,8 +278,14 @@ mod fs {
 /// Process environments are cheap to clone:
 /// - Faked process environments are wrapped in an internal Arc
 /// - Real process environments are pointer-sized
+///
+/// By default, keys are looked up exactly as given. [`Env::with_case_insensitive_lookup`] enables
+/// Windows-style lookups that ignore ASCII case.
 #[derive(Clone, Debug)]
-pub struct Env(env::Inner);
+pub struct Env {
+    inner: env::Inner,
+    case_insensitive: bool,
+}
 
 impl Default for Env {
     fn default() -> Self {
@@ -228,14 +295,54 @@ impl Default for Env {
 
 impl Env {
     /// Retrieve a value for the given `k` and return `VarError` is that key is not present.
+    ///
+    /// If case-insensitive lookup is enabled, an exact match is preferred over a match that
+    /// only differs in case.
     pub fn get(&self, k: &str) -> Result<String, VarError> {
         use env::Inner;
-        match &self.0 {
-            Inner::Real => std::env::var(k),
-            Inner::Fake(map) => map.get(k).cloned().ok_or(VarError::NotPresent),
+        match &self.inner {
+            Inner::Real => match std::env::var(k) {
+                Err(VarError::NotPresent) if self.case_insensitive => std::env::vars_os()
+                    .filter(|(key, _)| key.to_str().is_some_and(|key| key.eq_ignore_ascii_case(k)))
+                    .min_by(|(a, _), (b, _)| a.cmp(b))
+                    .ok_or(VarError::NotPresent)?
+                    .1
+                    .into_string()
+                    .map_err(VarError::NotUnicode),
+                result => result,
+            },
+            Inner::Fake(map) => match map.get(k) {
+                Some(value) => Ok(value.clone()),
+                None if self.case_insensitive => map
+                    .iter()
+                    .filter(|(key, _)| key.eq_ignore_ascii_case(k))
+                    .min_by_key(|(key, _)| *key)
+                    .map(|(_, value)| value.clone())
+                    .ok_or(VarError::NotPresent),
+                None => Err(VarError::NotPresent),
+            },
         }
     }
 
+    /// Enables or disables case-insensitive key lookup.
+    ///
+    /// When enabled, keys that only differ in ASCII case are treated as the same key, matching
+    /// how environment variables behave on Windows.
+    ///
+    /// # Examples
+    /// ```rust
+    /// use aws_types::os_shim_internal::Env;
+    /// let mock_env = Env::from_slice(&[("AWS_REGION", "us-west-2")]);
+    /// assert!(mock_env.get("aws_region").is_err());
+    ///
+    /// let mock_env = mock_env.with_case_insensitive_lookup(true);
+    /// assert_eq!(mock_env.get("aws_region").unwrap(), "us-west-2");
+    /// ```
+    pub fn with_case_insensitive_lookup(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
     /// Create a fake process environment from a slice of tuples.
     ///
     /// # Examples
@@ -259,13 +366,19 @@ impl Env {
     ///
     /// Calls will be delegated to [`std::env::var`].
     pub fn real() -> Self {
-        Self(env::Inner::Real)
+        Self {
+            inner: env::Inner::Real,
+            case_insensitive: false,
+        }
     }
 }
 
 impl From<HashMap<String, String>> for Env {
     fn from(hash_map: HashMap<String, String>) -> Self {
-        Self(env::Inner::Fake(Arc::new(hash_map)))
+        Self {
+            inner: env::Inner::Fake(Arc::new(hash_map)),
+            case_insensitive: false,
+        }
     }
 }
 
@@ -282,7 +395,10 @@ mod env {
 
 #[cfg(test)]
 mod test {
+    use std::collections::HashMap;
     use std::env::VarError;
+    use std::io::ErrorKind;
+    use std::path::PathBuf;
 
     use crate::os_shim_internal::{Env, Fs};
 
@@ -296,6 +412,44 @@ mod test {
         )
     }
 
+    #[test]
+    fn env_from_hash_map() {
+        let env = Env::from(HashMap::from([("FOO".to_owned(), "BAR".to_owned())]));
+        assert_eq!(env.get("FOO").unwrap(), "BAR");
+        assert_eq!(
+            env.get("foo").expect_err("case sensitive"),
+            VarError::NotPresent
+        );
+    }
+
+    #[test]
+    fn env_case_insensitive() {
+        let env = Env::from_slice(&[("Foo", "mixed"), ("OTHER", "other")])
+            .with_case_insensitive_lookup(true);
+        assert_eq!(env.get("Foo").unwrap(), "mixed");
+        assert_eq!(env.get("FOO").unwrap(), "mixed");
+        assert_eq!(env.get("foo").unwrap(), "mixed");
+        assert_eq!(env.get("other").unwrap(), "other");
+        assert_eq!(
+            env.get("MISSING").expect_err("absent"),
+            VarError::NotPresent
+        );
+
+        // Exact matches take priority
+        let env = Env::from_slice(&[("FOO", "upper"), ("foo", "lower")])
+            .with_case_insensitive_lookup(true);
+        assert_eq!(env.get("FOO").unwrap(), "upper");
+        assert_eq!(env.get("foo").unwrap(), "lower");
+        assert_eq!(env.get("Foo").unwrap(), "upper");
+
+        // And the mode can be turned back off
+        let env = env.with_case_insensitive_lookup(false);
+        assert_eq!(
+            env.get("Foo").expect_err("case sensitive"),
+            VarError::NotPresent
+        );
+    }
+
     #[tokio::test]
     async fn fs_from_test_dir_works() {
         let fs = Fs::from_test_dir(".", "/users/test-data");
@@ -325,4 +479,53 @@ mod test {
         let result = fs.read_to_end(&path).await.expect("success");
         assert_eq!(b"test", &result[..]);
     }
+
+    #[tokio::test]
+    async fn fs_from_path_map() {
+        let fs = Fs::from(HashMap::from([(
+            PathBuf::from("/home/.aws/config"),
+            b"[default]\nregion = us-east-1".to_vec(),
+        )]));
+
+        let contents = fs.read_to_end("/home/.aws/config").await.expect("exists");
+        assert_eq!(b"[default]\nregion = us-east-1", &contents[..]);
+
+        let err = fs
+            .read_to_end("/home/.aws/credentials")
+            .await
+            .expect_err("doesn't exist");
+        assert_eq!(ErrorKind::NotFound, err.kind());
+    }
+
+    #[tokio::test]
+    async fn fs_permission_denied() {
+        let fs = Fs::from_slice(&[("/home/.aws/config", "[default]")]);
+        let shared = fs.clone();
+        fs.deny_access("/home/.aws/config").unwrap();
+        // Access is denied through clones too
+        shared.deny_access("/home/.aws/credentials").unwrap();
+
+        let err = fs
+            .read_to_end("/home/.aws/config")
+            .await
+            .expect_err("denied");
+        assert_eq!(ErrorKind::PermissionDenied, err.kind());
+        // Files that don't exist are denied too, rather than missing
+        let err = fs
+            .read_to_end("/home/.aws/credentials")
+            .await
+            .expect_err("denied");
+        assert_eq!(ErrorKind::PermissionDenied, err.kind());
+        let err = fs
+            .write("/home/.aws/config", b"[profile other]")
+            .await
+            .expect_err("denied");
+        assert_eq!(ErrorKind::PermissionDenied, err.kind());
+    }
+
+    #[test]
+    fn deny_access_is_unsupported_on_real_fs() {
+        let err = Fs::real().deny_access("/etc/passwd").unwrap_err();
+        assert_eq!(ErrorKind::Unsupported, err.kind());
+    }
 }
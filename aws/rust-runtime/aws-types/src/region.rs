@@ -5,9 +5,13 @@
 
 //! Region type for determining the endpoint to send requests to.
 
+use crate::os_shim_internal::Env;
+use crate::SdkConfig;
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
 use std::borrow::Cow;
+use std::error::Error;
 use std::fmt::{Display, Formatter};
+use std::str::FromStr;
 
 /// The region to send requests to.
 ///
@@ -40,6 +44,31 @@ impl Storable for Region {
     type Storer = StoreReplace<Region>;
 }
 
+/// Borrows the region's name, without copying it.
+impl<'a> From<&'a Region> for Cow<'a, str> {
+    fn from(region: &'a Region) -> Self {
+        Cow::Borrowed(region.as_ref())
+    }
+}
+
+/// Converts into the region's name, which stays borrowed if the region was created from a static
+/// str.
+impl From<Region> for Cow<'static, str> {
+    fn from(region: Region) -> Self {
+        region.0
+    }
+}
+
+/// Legacy names and pseudo regions that [`Region::parse_validated`] maps to the region they stand for.
+const REGION_ALIASES: &[(&str, &str)] = &[
+    // S3's legacy location constraint for `eu-west-1`
+    ("EU", "eu-west-1"),
+    ("s3-external-1", "us-east-1"),
+    ("aws-global", "us-east-1"),
+    ("aws-cn-global", "cn-north-1"),
+    ("aws-us-gov-global", "us-gov-west-1"),
+];
+
 impl Region {
     /// Creates a new `Region` from the given string.
     pub fn new(region: impl Into<Cow<'static, str>>) -> Self {
@@ -50,8 +79,259 @@ impl Region {
     pub const fn from_static(region: &'static str) -> Self {
         Self(Cow::Borrowed(region))
     }
+
+    /// Creates a new `Region`, checking that it's shaped like an AWS region.
+    ///
+    /// A region is made up of at least three `-` separated parts of lowercase ASCII letters and
+    /// digits, where the first part is all letters and the last part is all digits, such as
+    /// `us-east-1`, `cn-north-1`, or `us-gov-west-1`. This doesn't check that the region exists,
+    /// so new regions will continue to be accepted. Use [`Region::new`] to skip this check.
+    ///
+    /// A few legacy names and pseudo regions, such as S3's `EU` or `aws-global`, are accepted
+    /// too, and are replaced by the region they stand for. Any other value is kept as-is.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use aws_types::region::Region;
+    ///
+    /// assert!(Region::parse_validated("us-gov-west-1").is_ok());
+    /// assert!(Region::parse_validated("US East (N. Virginia)").is_err());
+    /// assert_eq!(Region::parse_validated("aws-global").unwrap(), Region::from_static("us-east-1"));
+    /// ```
+    pub fn parse_validated(region: &str) -> Result<Self, InvalidRegion> {
+        let parsed: Region = region.parse()?;
+        if let Some((_, canonical)) = REGION_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == parsed.as_ref())
+        {
+            return Ok(Region::from_static(canonical));
+        }
+        let region = parsed.as_ref();
+        if let Some(c) = region
+            .chars()
+            .find(|c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || *c == '-'))
+        {
+            return Err(InvalidRegion::new(InvalidRegionKind::InvalidCharacter {
+                region: region.to_owned(),
+                character: c,
+            }));
+        }
+        let parts: Vec<&str> = region.split('-').collect();
+        let well_formed = parts.len() >= 3
+            && parts.iter().all(|part| !part.is_empty())
+            && parts[0].chars().all(|c| c.is_ascii_lowercase())
+            && parts[parts.len() - 1].chars().all(|c| c.is_ascii_digit());
+        if !well_formed {
+            return Err(InvalidRegion::new(InvalidRegionKind::Malformed {
+                region: region.to_owned(),
+            }));
+        }
+        Ok(parsed)
+    }
+
+    /// Returns the partition this region belongs to, based on its prefix.
+    ///
+    /// Regions that don't belong to a known partition are assumed to be in the standard `aws`
+    /// partition, since that is where new regions are usually launched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use aws_types::region::{Partition, Region};
+    ///
+    /// assert_eq!(Partition::Aws, Region::from_static("us-east-1").partition());
+    /// assert_eq!(Partition::AwsCn, Region::from_static("cn-north-1").partition());
+    /// assert_eq!("aws-us-gov", Region::from_static("us-gov-west-1").partition().to_string());
+    /// ```
+    pub fn partition(&self) -> Partition {
+        // Longer prefixes come first, so that `us-isob-` isn't mistaken for `us-iso-`.
+        const PREFIXES: &[(&str, Partition)] = &[
+            ("us-gov-", Partition::AwsUsGov),
+            ("us-isob-", Partition::AwsIsoB),
+            ("us-isof-", Partition::AwsIsoF),
+            ("eu-isoe-", Partition::AwsIsoE),
+            ("us-iso-", Partition::AwsIso),
+            ("cn-", Partition::AwsCn),
+        ];
+        PREFIXES
+            .iter()
+            .find(|(prefix, _)| self.0.starts_with(prefix))
+            .map(|(_, partition)| *partition)
+            .unwrap_or(Partition::Aws)
+    }
 }
 
+/// A group of AWS regions that share endpoints and credentials.
+///
+/// The [`Display`] implementation produces the partition's ID, such as `aws-cn`.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Partition {
+    /// The standard `aws` partition.
+    Aws,
+    /// The `aws-cn` partition, for the China regions.
+    AwsCn,
+    /// The `aws-us-gov` partition, for the AWS GovCloud (US) regions.
+    AwsUsGov,
+    /// The `aws-iso` partition.
+    AwsIso,
+    /// The `aws-iso-b` partition.
+    AwsIsoB,
+    /// The `aws-iso-e` partition.
+    AwsIsoE,
+    /// The `aws-iso-f` partition.
+    AwsIsoF,
+}
+
+impl Partition {
+    /// Returns the ID of this partition, such as `aws-cn`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Partition::Aws => "aws",
+            Partition::AwsCn => "aws-cn",
+            Partition::AwsUsGov => "aws-us-gov",
+            Partition::AwsIso => "aws-iso",
+            Partition::AwsIsoB => "aws-iso-b",
+            Partition::AwsIsoE => "aws-iso-e",
+            Partition::AwsIsoF => "aws-iso-f",
+        }
+    }
+}
+
+impl Display for Partition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Parses a `Region`, trimming any surrounding whitespace.
+///
+/// This doesn't check that the region exists, only that it isn't empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use aws_types::region::Region;
+///
+/// let region: Region = " us-west-2 ".parse().unwrap();
+/// assert_eq!(Region::from_static("us-west-2"), region);
+/// assert!("".parse::<Region>().is_err());
+/// ```
+impl FromStr for Region {
+    type Err = InvalidRegion;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let region = s.trim();
+        if region.is_empty() {
+            return Err(InvalidRegion::new(InvalidRegionKind::Empty));
+        }
+        Ok(Region::new(region.to_owned()))
+    }
+}
+
+#[derive(Debug)]
+enum InvalidRegionKind {
+    Empty,
+    InvalidCharacter { region: String, character: char },
+    Malformed { region: String },
+}
+
+/// Error for when a string can't be parsed into a [`Region`].
+#[derive(Debug)]
+pub struct InvalidRegion {
+    kind: InvalidRegionKind,
+}
+
+impl InvalidRegion {
+    fn new(kind: InvalidRegionKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl Display for InvalidRegion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            InvalidRegionKind::Empty => write!(f, "a region must not be empty"),
+            InvalidRegionKind::InvalidCharacter { region, character } => write!(
+                f,
+                "`{region}` is not a valid region: `{character}` is not allowed \
+                 (only lowercase letters, digits, and `-` are)"
+            ),
+            InvalidRegionKind::Malformed { region } => write!(
+                f,
+                "`{region}` is not a valid region: expected a region like `us-east-1`"
+            ),
+        }
+    }
+}
+
+impl Error for InvalidRegion {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Region {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_ref())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Region {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let region = String::deserialize(deserializer)?;
+        region.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Returns the region the SDK would use for `config`.
+///
+/// A region set on `config` takes precedence. Otherwise, the `AWS_REGION` environment variable is
+/// used, falling back to `AWS_DEFAULT_REGION`. Environment variables that are empty are skipped.
+///
+/// Unlike `aws-config`, this doesn't look at the shared config files or at IMDS, so it's only
+/// meant for an `SdkConfig` that has already been loaded.
+///
+/// # Examples
+///
+/// ```rust
+/// use aws_types::os_shim_internal::Env;
+/// use aws_types::region::{effective_region, Region};
+/// use aws_types::SdkConfig;
+///
+/// let env = Env::from_slice(&[("AWS_REGION", "us-west-2")]);
+/// let config = SdkConfig::builder().build();
+/// assert_eq!(Region::from_static("us-west-2"), effective_region(&config, &env).unwrap());
+/// ```
+pub fn effective_region(config: &SdkConfig, env: &Env) -> Result<Region, NoRegion> {
+    if let Some(region) = config.region() {
+        return Ok(region.clone());
+    }
+    ["AWS_REGION", "AWS_DEFAULT_REGION"]
+        .into_iter()
+        .find_map(|var| env.get(var).ok()?.parse().ok())
+        .ok_or(NoRegion { _private: () })
+}
+
+/// Error for when neither an `SdkConfig` nor the environment specify a region.
+///
+/// See [`effective_region`].
+#[derive(Debug)]
+pub struct NoRegion {
+    _private: (),
+}
+
+impl Display for NoRegion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "no region is configured: set one on the `SdkConfig`, or with the \
+             `AWS_REGION` or `AWS_DEFAULT_REGION` environment variables"
+        )
+    }
+}
+
+impl Error for NoRegion {}
+
 /// The region to use when signing requests
 ///
 /// Generally, user code will not need to interact with `SigningRegion`. See `[Region](crate::Region)`.
@@ -133,3 +413,215 @@ impl AsRef<str> for SigningRegionSet {
         self.0.as_ref()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{effective_region, Partition, Region};
+    use crate::os_shim_internal::Env;
+    use crate::SdkConfig;
+    use std::borrow::Cow;
+
+    #[test]
+    fn round_trip() {
+        for region in ["us-east-1", "cn-north-1", "us-gov-west-1", "eu-central-2"] {
+            let parsed: Region = region.parse().unwrap();
+            assert_eq!(Region::from_static(region), parsed);
+            assert_eq!(region, parsed.to_string());
+        }
+    }
+
+    #[test]
+    fn surrounding_whitespace_is_trimmed() {
+        assert_eq!(
+            Region::from_static("us-east-1"),
+            " us-east-1\n".parse::<Region>().unwrap()
+        );
+    }
+
+    #[test]
+    fn validated_regions_across_partitions() {
+        for region in [
+            "us-east-1",
+            "eu-west-3",
+            "ap-southeast-5",
+            "cn-north-1",
+            "cn-northwest-1",
+            "us-gov-west-1",
+            "us-iso-east-1",
+            "us-isob-east-1",
+            "eu-isoe-west-1",
+        ] {
+            assert_eq!(
+                Region::from_static(region),
+                Region::parse_validated(region).unwrap()
+            );
+        }
+        // Regions that don't exist yet but have the right shape are still accepted
+        assert!(Region::parse_validated("xx-newregion-42").is_ok());
+    }
+
+    #[test]
+    fn aliases_are_replaced_by_canonical_regions() {
+        for (alias, canonical) in [
+            ("EU", "eu-west-1"),
+            (" aws-global ", "us-east-1"),
+            ("s3-external-1", "us-east-1"),
+            ("aws-us-gov-global", "us-gov-west-1"),
+        ] {
+            let region = Region::parse_validated(alias).unwrap();
+            assert_eq!(Region::from_static(canonical), region, "`{alias}`");
+            assert!(matches!(region.0, Cow::Borrowed(_)));
+        }
+
+        // Regions that aren't aliases are passed through, even if they look like one
+        for region in ["eu-west-1", "eu-west-42", "aws-global-1"] {
+            assert_eq!(
+                Region::from_static(region),
+                Region::parse_validated(region).unwrap()
+            );
+        }
+        assert!(Region::parse_validated("eu").is_err());
+    }
+
+    #[test]
+    fn malformed_regions_are_rejected() {
+        for region in [
+            "",
+            "US-EAST-1",
+            "us_east_1",
+            "us east 1",
+            "us-east",
+            "useast1",
+            "us-east-",
+            "us--east-1",
+            "-us-east-1",
+            "1-east-1",
+            "us-east-1a",
+        ] {
+            assert!(
+                Region::parse_validated(region).is_err(),
+                "`{region}` should be rejected"
+            );
+        }
+        assert_eq!(
+            "`us_east_1` is not a valid region: `_` is not allowed \
+             (only lowercase letters, digits, and `-` are)",
+            Region::parse_validated("us_east_1")
+                .unwrap_err()
+                .to_string()
+        );
+        assert_eq!(
+            "`us-east` is not a valid region: expected a region like `us-east-1`",
+            Region::parse_validated("us-east").unwrap_err().to_string()
+        );
+        // Unvalidated regions are still allowed
+        assert_eq!("us-east", Region::new("us-east").as_ref());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let region = Region::from_static("us-west-2");
+        let json = serde_json::to_string(&region).unwrap();
+        assert_eq!("\"us-west-2\"", json);
+        assert_eq!(region, serde_json::from_str::<Region>(&json).unwrap());
+        assert!(serde_json::from_str::<Region>("\"\"").is_err());
+    }
+
+    #[test]
+    fn empty_region_is_rejected() {
+        let err = "".parse::<Region>().expect_err("empty region");
+        assert_eq!("a region must not be empty", err.to_string());
+        assert!("  \t".parse::<Region>().is_err());
+    }
+
+    #[test]
+    fn effective_region_from_config() {
+        let config = SdkConfig::builder()
+            .region(Region::from_static("eu-west-1"))
+            .build();
+        assert_eq!(
+            Region::from_static("eu-west-1"),
+            effective_region(&config, &Env::from_slice(&[])).unwrap()
+        );
+    }
+
+    #[test]
+    fn effective_region_from_env() {
+        let config = SdkConfig::builder().build();
+        let env = Env::from_slice(&[
+            ("AWS_REGION", "us-east-1"),
+            ("AWS_DEFAULT_REGION", "us-east-2"),
+        ]);
+        assert_eq!(
+            Region::from_static("us-east-1"),
+            effective_region(&config, &env).unwrap()
+        );
+
+        let env = Env::from_slice(&[("AWS_REGION", ""), ("AWS_DEFAULT_REGION", "us-east-2")]);
+        assert_eq!(
+            Region::from_static("us-east-2"),
+            effective_region(&config, &env).unwrap()
+        );
+    }
+
+    #[test]
+    fn config_region_overrides_env() {
+        let config = SdkConfig::builder()
+            .region(Region::from_static("eu-west-1"))
+            .build();
+        let env = Env::from_slice(&[("AWS_REGION", "us-east-1")]);
+        assert_eq!(
+            Region::from_static("eu-west-1"),
+            effective_region(&config, &env).unwrap()
+        );
+    }
+
+    #[test]
+    fn no_region() {
+        let config = SdkConfig::builder().build();
+        let err = effective_region(&config, &Env::from_slice(&[("AWS_REGION", " ")]))
+            .expect_err("no region is configured");
+        assert!(err.to_string().contains("AWS_REGION"), "{err}");
+    }
+
+    #[test]
+    fn partition() {
+        for (region, partition) in [
+            ("us-east-1", "aws"),
+            ("eu-central-2", "aws"),
+            ("cn-northwest-1", "aws-cn"),
+            ("us-gov-east-1", "aws-us-gov"),
+            ("us-iso-east-1", "aws-iso"),
+            ("us-isob-east-1", "aws-iso-b"),
+            ("eu-isoe-west-1", "aws-iso-e"),
+            ("us-isof-south-1", "aws-iso-f"),
+        ] {
+            assert_eq!(
+                partition,
+                Region::from_static(region).partition().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_region_defaults_to_aws_partition() {
+        assert_eq!(Partition::Aws, Region::from_static("xx-moon-1").partition());
+        assert_eq!(Partition::Aws, Region::from_static("local").partition());
+    }
+
+    #[test]
+    fn into_cow() {
+        let region = Region::new("us-west-2".to_owned());
+        let borrowed: Cow<'_, str> = (&region).into();
+        assert!(matches!(borrowed, Cow::Borrowed("us-west-2")));
+        assert_eq!(region.as_ref().as_ptr(), borrowed.as_ptr());
+
+        let owned: Cow<'static, str> = region.clone().into();
+        assert!(matches!(owned, Cow::Owned(_)));
+        assert_eq!("us-west-2", owned);
+
+        let from_static: Cow<'static, str> = Region::from_static("eu-west-1").into();
+        assert!(matches!(from_static, Cow::Borrowed("eu-west-1")));
+    }
+}
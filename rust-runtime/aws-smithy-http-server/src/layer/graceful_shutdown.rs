@@ -0,0 +1,357 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Middleware for draining in-flight requests before shutting down.
+//!
+//! Once [`GracefulShutdown::trigger`] has been called, new requests are refused with a
+//! `503 Service Unavailable` and a `Connection: close` header, while requests that were already
+//! being handled are allowed to complete. [`GracefulShutdown::drained`] resolves once they have.
+//!
+//! A request is in flight until its response body has been dropped, which the server does once
+//! the body has been sent, so streaming responses are drained too.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aws_smithy_http_server::layer::graceful_shutdown::GracefulShutdown;
+//! use tower::Layer;
+//!
+//! # async fn run() {
+//! let shutdown = GracefulShutdown::new();
+//! # async fn handle(_req: hyper::Request<hyper::Body>) -> Result<http::Response<aws_smithy_http_server::body::BoxBody>, std::convert::Infallible> { todo!() }
+//! let app = tower::service_fn(handle);
+//! let app = shutdown.layer().layer(app);
+//!
+//! // Serve `app`, and when the process is asked to stop:
+//! tokio::signal::ctrl_c().await.unwrap();
+//! shutdown.shutdown().await;
+//! # }
+//! ```
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http::{header, HeaderMap, Request, Response, StatusCode};
+use http_body::{Body, SizeHint};
+use pin_project_lite::pin_project;
+use tokio::sync::Notify;
+use tower::{Layer, Service};
+
+use crate::body::BoxBody;
+
+#[derive(Debug, Default)]
+struct State {
+    shutting_down: AtomicBool,
+    in_flight: AtomicUsize,
+    drained: Notify,
+}
+
+/// A handle used to shut down every service wrapped in its [`GracefulShutdownLayer`].
+#[derive(Clone, Debug, Default)]
+pub struct GracefulShutdown {
+    state: Arc<State>,
+}
+
+impl GracefulShutdown {
+    /// Creates a handle that has not been triggered.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a [`tower::Layer`] whose services stop accepting requests once this handle is
+    /// triggered.
+    pub fn layer(&self) -> GracefulShutdownLayer {
+        GracefulShutdownLayer {
+            state: self.state.clone(),
+        }
+    }
+
+    /// Stops accepting new requests.
+    pub fn trigger(&self) {
+        self.state.shutting_down.store(true, Ordering::SeqCst);
+    }
+
+    /// Returns `true` if this handle has been triggered.
+    pub fn is_triggered(&self) -> bool {
+        self.state.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Returns the number of requests currently being handled, including those whose response body
+    /// is still being sent.
+    pub fn in_flight(&self) -> usize {
+        self.state.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once no requests are being handled.
+    ///
+    /// This does not trigger the handle, so unless [`GracefulShutdown::trigger`] has been called,
+    /// new requests may still be accepted after it resolves.
+    pub async fn drained(&self) {
+        loop {
+            // Created before checking the count so that a notification sent in between isn't missed.
+            let notified = self.state.drained.notified();
+            if self.in_flight() == 0 {
+                return;
+            }
+            notified.await;
+        }
+    }
+
+    /// Stops accepting new requests, and resolves once in-flight requests have completed.
+    pub async fn shutdown(&self) {
+        self.trigger();
+        self.drained().await
+    }
+}
+
+/// A [`tower::Layer`] used to apply [`GracefulShutdownService`].
+///
+/// It is created with [`GracefulShutdown::layer`].
+#[derive(Clone, Debug)]
+pub struct GracefulShutdownLayer {
+    state: Arc<State>,
+}
+
+impl<S> Layer<S> for GracefulShutdownLayer {
+    type Service = GracefulShutdownService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GracefulShutdownService {
+            inner,
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// A middleware [`Service`] responsible for refusing requests once its [`GracefulShutdown`]
+/// handle has been triggered, and for tracking the requests it is handling.
+#[derive(Clone, Debug)]
+pub struct GracefulShutdownService<S> {
+    inner: S,
+    state: Arc<State>,
+}
+
+impl<S, B> Service<Request<B>> for GracefulShutdownService<S>
+where
+    S: Service<Request<B>, Response = Response<BoxBody>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = GracefulShutdownFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        // Count the request before checking for shutdown, so that `drained` can't resolve while it
+        // is being let through.
+        let guard = InFlightGuard::new(self.state.clone());
+        if self.state.shutting_down.load(Ordering::SeqCst) {
+            drop(guard);
+            return GracefulShutdownFuture {
+                inner: None,
+                guard: None,
+            };
+        }
+        GracefulShutdownFuture {
+            inner: Some(self.inner.call(req)),
+            guard: Some(guard),
+        }
+    }
+}
+
+// Decrements the in-flight count when the response body is dropped, or the request is cancelled.
+struct InFlightGuard {
+    state: Arc<State>,
+}
+
+impl InFlightGuard {
+    fn new(state: Arc<State>) -> Self {
+        state.in_flight.fetch_add(1, Ordering::SeqCst);
+        Self { state }
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self.state.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.state.drained.notify_waiters();
+        }
+    }
+}
+
+fn service_unavailable() -> Response<BoxBody> {
+    Response::builder()
+        .status(StatusCode::SERVICE_UNAVAILABLE)
+        .header(header::CONNECTION, "close")
+        .body(crate::body::empty())
+        .expect("invalid HTTP response for service unavailable; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues")
+}
+
+pin_project! {
+    /// Future for [`GracefulShutdownService`].
+    pub struct GracefulShutdownFuture<F> {
+        // `None` if the request was refused without calling the inner service.
+        #[pin]
+        inner: Option<F>,
+        // Moved into the response body once the inner service responds.
+        guard: Option<InFlightGuard>,
+    }
+}
+
+impl<F, E> Future for GracefulShutdownFuture<F>
+where
+    F: Future<Output = Result<Response<BoxBody>, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let inner = match this.inner.as_pin_mut() {
+            Some(inner) => inner,
+            None => return Poll::Ready(Ok(service_unavailable())),
+        };
+        let res = futures_util::ready!(inner.poll(cx));
+        let guard = this.guard.take();
+        Poll::Ready(res.map(|res| {
+            res.map(|body| {
+                crate::body::boxed(GuardedBody {
+                    inner: body,
+                    _guard: guard,
+                })
+            })
+        }))
+    }
+}
+
+pin_project! {
+    // Keeps a request in flight until its response body is dropped.
+    struct GuardedBody {
+        #[pin]
+        inner: BoxBody,
+        _guard: Option<InFlightGuard>,
+    }
+}
+
+impl Body for GuardedBody {
+    type Data = Bytes;
+    type Error = crate::Error;
+
+    fn poll_data(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        self.project().inner.poll_data(cx)
+    }
+
+    fn poll_trailers(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        self.project().inner.poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::Infallible;
+    use std::time::Duration;
+    use tokio::sync::Semaphore;
+    use tower::{service_fn, ServiceExt};
+
+    #[tokio::test]
+    async fn in_flight_requests_complete_and_new_requests_are_refused() {
+        tokio::time::pause();
+        let shutdown = GracefulShutdown::new();
+        // Handlers wait for a permit, so they stay in flight until the test releases them.
+        let release = Arc::new(Semaphore::new(0));
+        let svc = shutdown.layer().layer(service_fn({
+            let release = release.clone();
+            move |_req: Request<()>| {
+                let release = release.clone();
+                async move {
+                    release.acquire().await.unwrap().forget();
+                    Ok::<_, Infallible>(Response::new(crate::body::empty()))
+                }
+            }
+        }));
+
+        let first = tokio::spawn(svc.clone().oneshot(Request::new(())));
+        let second = tokio::spawn(svc.clone().oneshot(Request::new(())));
+        while shutdown.in_flight() < 2 {
+            tokio::task::yield_now().await;
+        }
+
+        shutdown.trigger();
+        let drained = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.drained().await }
+        });
+
+        let refused = svc.clone().oneshot(Request::new(())).await.unwrap();
+        assert_eq!(refused.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(refused.headers()[header::CONNECTION], "close");
+        assert_eq!(shutdown.in_flight(), 2);
+
+        tokio::time::advance(Duration::from_millis(10)).await;
+        assert!(!drained.is_finished(), "requests are still in flight");
+
+        release.add_permits(2);
+        assert_eq!(first.await.unwrap().unwrap().status(), StatusCode::OK);
+        assert_eq!(second.await.unwrap().unwrap().status(), StatusCode::OK);
+        tokio::time::timeout(Duration::from_secs(5), drained)
+            .await
+            .expect("drained once in-flight requests completed")
+            .unwrap();
+        assert_eq!(shutdown.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn streaming_responses_are_drained_once_their_body_is_dropped() {
+        tokio::time::pause();
+        let shutdown = GracefulShutdown::new();
+        let (mut sender, body) = hyper::Body::channel();
+        let body = std::sync::Mutex::new(Some(body));
+        let svc = shutdown.layer().layer(service_fn(move |_req: Request<()>| {
+            let body = body.lock().unwrap().take().expect("only one request is sent");
+            async move { Ok::<_, Infallible>(Response::new(crate::body::boxed(body))) }
+        }));
+
+        let res = svc.oneshot(Request::new(())).await.unwrap();
+        assert_eq!(shutdown.in_flight(), 1);
+        let drained = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.shutdown().await }
+        });
+        tokio::time::advance(Duration::from_millis(10)).await;
+        assert!(!drained.is_finished(), "the response body is still being sent");
+
+        let collected = tokio::spawn(hyper::body::to_bytes(res.into_body()));
+        sender.send_data(Bytes::from_static(b"streamed")).await.unwrap();
+        drop(sender);
+        assert_eq!(&collected.await.unwrap().unwrap()[..], b"streamed");
+        tokio::time::timeout(Duration::from_secs(5), drained)
+            .await
+            .expect("drained once the response body was sent")
+            .unwrap();
+        assert_eq!(shutdown.in_flight(), 0);
+    }
+
+    #[tokio::test]
+    async fn shutdown_without_requests_resolves_immediately() {
+        let shutdown = GracefulShutdown::new();
+        assert!(!shutdown.is_triggered());
+        shutdown.shutdown().await;
+        assert!(shutdown.is_triggered());
+    }
+}
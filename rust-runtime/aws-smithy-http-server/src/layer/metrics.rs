@@ -0,0 +1,373 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Middleware for recording per-operation request metrics.
+//!
+//! For every request, [`Metrics`] reports the operation it was routed to, the class of the
+//! response's status code, and how long the handler took to respond to a [`MetricsRecorder`].
+//! Request and error counts follow from counting these records. Bridging to a metrics backend is
+//! a matter of implementing [`MetricsRecorder`].
+//!
+//! Because the operation is only known once a request has been routed, [`MetricsPlugin`] should be
+//! applied as an HTTP plugin, which works the same for every protocol's router.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aws_smithy_http_server::layer::metrics::{MetricsPlugin, MetricsRecorder, StatusClass};
+//! use aws_smithy_http_server::plugin::HttpPlugins;
+//! use aws_smithy_http_server::shape_id::ShapeId;
+//! use std::time::Duration;
+//!
+//! struct LogRecorder;
+//!
+//! impl MetricsRecorder for LogRecorder {
+//!     fn record(&self, operation: ShapeId, status: StatusClass, duration: Duration) {
+//!         println!("{}: {status:?} in {duration:?}", operation.absolute());
+//!     }
+//! }
+//!
+//! let http_plugins = HttpPlugins::new().push(MetricsPlugin::new(LogRecorder));
+//! ```
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+
+use http::{Request, Response, StatusCode};
+use pin_project_lite::pin_project;
+use tokio::time::Instant;
+use tower::{Layer, Service};
+
+use crate::operation::OperationShape;
+use crate::plugin::{HttpMarker, Plugin};
+use crate::shape_id::ShapeId;
+
+/// The class of an HTTP response status code.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StatusClass {
+    /// `1xx` status codes.
+    Informational,
+    /// `2xx` status codes.
+    Success,
+    /// `3xx` status codes.
+    Redirection,
+    /// `4xx` status codes.
+    ClientError,
+    /// `5xx` status codes, or the service failing to produce a response at all.
+    ServerError,
+}
+
+impl From<StatusCode> for StatusClass {
+    fn from(status: StatusCode) -> Self {
+        if status.is_informational() {
+            StatusClass::Informational
+        } else if status.is_success() {
+            StatusClass::Success
+        } else if status.is_redirection() {
+            StatusClass::Redirection
+        } else if status.is_client_error() {
+            StatusClass::ClientError
+        } else {
+            StatusClass::ServerError
+        }
+    }
+}
+
+/// Receives the metrics recorded by [`Metrics`].
+pub trait MetricsRecorder: Send + Sync + 'static {
+    /// Records that a request to `operation` was responded to with a status code of class
+    /// `status`, after `duration`.
+    fn record(&self, operation: ShapeId, status: StatusClass, duration: Duration);
+}
+
+impl<R: MetricsRecorder> MetricsRecorder for Arc<R> {
+    fn record(&self, operation: ShapeId, status: StatusClass, duration: Duration) {
+        self.as_ref().record(operation, status, duration)
+    }
+}
+
+/// A [`tower::Layer`] used to apply [`Metrics`].
+pub struct MetricsLayer<R> {
+    operation: ShapeId,
+    recorder: Arc<R>,
+}
+
+impl<R> MetricsLayer<R> {
+    /// Records metrics for requests to `operation` with `recorder`.
+    pub fn new(operation: ShapeId, recorder: Arc<R>) -> Self {
+        Self { operation, recorder }
+    }
+}
+
+impl<R> Clone for MetricsLayer<R> {
+    fn clone(&self) -> Self {
+        Self::new(self.operation.clone(), self.recorder.clone())
+    }
+}
+
+impl<R> fmt::Debug for MetricsLayer<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricsLayer")
+            .field("operation", &self.operation)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, R> Layer<S> for MetricsLayer<R> {
+    type Service = Metrics<S, R>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Metrics::new(inner, self.operation.clone(), self.recorder.clone())
+    }
+}
+
+/// A middleware [`Service`] responsible for recording metrics for requests to an operation.
+pub struct Metrics<S, R> {
+    inner: S,
+    operation: ShapeId,
+    recorder: Arc<R>,
+}
+
+impl<S, R> Metrics<S, R> {
+    /// Wraps `inner`, recording metrics for requests to `operation` with `recorder`.
+    pub fn new(inner: S, operation: ShapeId, recorder: Arc<R>) -> Self {
+        Self {
+            inner,
+            operation,
+            recorder,
+        }
+    }
+}
+
+impl<S: Clone, R> Clone for Metrics<S, R> {
+    fn clone(&self) -> Self {
+        Self::new(self.inner.clone(), self.operation.clone(), self.recorder.clone())
+    }
+}
+
+impl<S: fmt::Debug, R> fmt::Debug for Metrics<S, R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Metrics")
+            .field("inner", &self.inner)
+            .field("operation", &self.operation)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S, R, B, RespB> Service<Request<B>> for Metrics<S, R>
+where
+    S: Service<Request<B>, Response = Response<RespB>>,
+    R: MetricsRecorder,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = MetricsFuture<S::Future, R>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        MetricsFuture {
+            inner: self.inner.call(req),
+            operation: Some(self.operation.clone()),
+            recorder: self.recorder.clone(),
+            start: Instant::now(),
+        }
+    }
+}
+
+pin_project! {
+    /// Future for [`Metrics`].
+    pub struct MetricsFuture<F, R> {
+        #[pin]
+        inner: F,
+        // Taken once the metrics have been recorded.
+        operation: Option<ShapeId>,
+        recorder: Arc<R>,
+        start: Instant,
+    }
+}
+
+impl<F, R, RespB, E> Future for MetricsFuture<F, R>
+where
+    F: Future<Output = Result<Response<RespB>, E>>,
+    R: MetricsRecorder,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let res = ready!(this.inner.poll(cx));
+        if let Some(operation) = this.operation.take() {
+            let status = match &res {
+                Ok(res) => res.status().into(),
+                Err(_) => StatusClass::ServerError,
+            };
+            this.recorder.record(operation, status, this.start.elapsed());
+        }
+        Poll::Ready(res)
+    }
+}
+
+/// A [`Plugin`] which applies [`Metrics`] to every operation, recording to a shared
+/// [`MetricsRecorder`].
+pub struct MetricsPlugin<R> {
+    recorder: Arc<R>,
+}
+
+impl<R> MetricsPlugin<R> {
+    /// Records metrics for every operation with `recorder`.
+    pub fn new(recorder: R) -> Self {
+        Self {
+            recorder: Arc::new(recorder),
+        }
+    }
+}
+
+impl<R> Clone for MetricsPlugin<R> {
+    fn clone(&self) -> Self {
+        Self {
+            recorder: self.recorder.clone(),
+        }
+    }
+}
+
+impl<R> fmt::Debug for MetricsPlugin<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MetricsPlugin").finish_non_exhaustive()
+    }
+}
+
+impl<Ser, Op, T, R> Plugin<Ser, Op, T> for MetricsPlugin<R>
+where
+    Op: OperationShape,
+{
+    type Output = Metrics<T, R>;
+
+    fn apply(&self, input: T) -> Self::Output {
+        Metrics::new(input, Op::ID, self.recorder.clone())
+    }
+}
+
+impl<R> HttpMarker for MetricsPlugin<R> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body::{empty, BoxBody};
+    use crate::protocol::rpc_v2::router::RpcV2Router;
+    use crate::protocol::rpc_v2::RpcV2;
+    use crate::routing::RoutingService;
+    use std::convert::Infallible;
+    use std::sync::Mutex;
+    use tower::{service_fn, ServiceExt};
+
+    #[derive(Default)]
+    struct TestRecorder {
+        records: Mutex<Vec<(&'static str, StatusClass)>>,
+    }
+
+    impl MetricsRecorder for TestRecorder {
+        fn record(&self, operation: ShapeId, status: StatusClass, _duration: Duration) {
+            self.records.lock().unwrap().push((operation.name(), status));
+        }
+    }
+
+    struct GetThing;
+    impl OperationShape for GetThing {
+        const ID: ShapeId = ShapeId::new("com.example#GetThing", "com.example", "GetThing");
+        type Input = ();
+        type Output = ();
+        type Error = Infallible;
+    }
+
+    struct PutThing;
+    impl OperationShape for PutThing {
+        const ID: ShapeId = ShapeId::new("com.example#PutThing", "com.example", "PutThing");
+        type Input = ();
+        type Output = ();
+        type Error = Infallible;
+    }
+
+    fn respond_with<B>(
+        status: StatusCode,
+    ) -> impl Service<Request<B>, Response = Response<BoxBody>, Error = Infallible, Future = impl Send> + Clone {
+        service_fn(move |_req| async move {
+            let mut res = Response::new(empty());
+            *res.status_mut() = status;
+            Ok(res)
+        })
+    }
+
+    #[test]
+    fn status_classes() {
+        assert_eq!(StatusClass::from(StatusCode::CONTINUE), StatusClass::Informational);
+        assert_eq!(StatusClass::from(StatusCode::NO_CONTENT), StatusClass::Success);
+        assert_eq!(StatusClass::from(StatusCode::NOT_MODIFIED), StatusClass::Redirection);
+        assert_eq!(StatusClass::from(StatusCode::NOT_FOUND), StatusClass::ClientError);
+        assert_eq!(StatusClass::from(StatusCode::BAD_GATEWAY), StatusClass::ServerError);
+    }
+
+    #[tokio::test]
+    async fn records_operation_and_status_class() {
+        let recorder = Arc::new(TestRecorder::default());
+        let plugin = MetricsPlugin::new(recorder.clone());
+        let get = Plugin::<(), GetThing, _>::apply(&plugin, respond_with(StatusCode::OK));
+        let put = Plugin::<(), PutThing, _>::apply(&plugin, respond_with(StatusCode::SERVICE_UNAVAILABLE));
+
+        get.clone().oneshot(Request::new(())).await.unwrap();
+        get.oneshot(Request::new(())).await.unwrap();
+        put.oneshot(Request::new(())).await.unwrap();
+
+        assert_eq!(
+            *recorder.records.lock().unwrap(),
+            vec![
+                ("GetThing", StatusClass::Success),
+                ("GetThing", StatusClass::Success),
+                ("PutThing", StatusClass::ServerError),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn records_through_rpc_v2_router() {
+        let recorder = Arc::new(TestRecorder::default());
+        let plugin = MetricsPlugin::new(recorder.clone());
+        let router: RpcV2Router<_> = [
+            (
+                "Service.GetThing".to_string(),
+                Plugin::<(), GetThing, _>::apply(&plugin, respond_with(StatusCode::OK)).boxed_clone(),
+            ),
+            (
+                "Service.PutThing".to_string(),
+                Plugin::<(), PutThing, _>::apply(&plugin, respond_with(StatusCode::BAD_REQUEST)).boxed_clone(),
+            ),
+        ]
+        .into_iter()
+        .collect();
+        let svc = RoutingService::<_, RpcV2>::new(router);
+
+        for operation in ["GetThing", "PutThing"] {
+            let req = Request::post(format!("/service/Service/operation/{operation}"))
+                .body(hyper::Body::empty())
+                .unwrap();
+            svc.clone().oneshot(req).await.unwrap().await.unwrap();
+        }
+
+        assert_eq!(
+            *recorder.records.lock().unwrap(),
+            vec![
+                ("GetThing", StatusClass::Success),
+                ("PutThing", StatusClass::ClientError)
+            ]
+        );
+    }
+}
@@ -7,3 +7,7 @@
 //! [`Router`](crate::routing::Router), so they are enacted before a request is routed.
 
 pub mod alb_health_check;
+pub mod graceful_shutdown;
+pub mod metrics;
+pub mod request_body_limit;
+pub mod timeout;
@@ -0,0 +1,469 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Middleware for rejecting oversized request bodies with a `413 Payload Too Large`.
+//!
+//! Requests whose `Content-Length` exceeds the limit are rejected before the inner service is
+//! called. Other requests are passed on with a [`LimitedBody`], which errors once more than the
+//! limit has been read, and the inner service's response is then replaced with a `413`, so at most
+//! the limit is ever buffered. The `413` is a [`PayloadTooLargeException`], serialized according to
+//! the protocol `P`. Trailers, such as trailing checksums, are passed through unchanged.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aws_smithy_http_server::layer::request_body_limit::RequestBodyLimitLayer;
+//! use aws_smithy_http_server::protocol::rest_json_1::RestJson1;
+//! use tower::Layer;
+//!
+//! // Reject request bodies larger than 1 MiB.
+//! let limit_layer = RequestBodyLimitLayer::<RestJson1>::new(1024 * 1024);
+//! # use aws_smithy_http_server::layer::request_body_limit::LimitedBody;
+//! # async fn handle(_req: hyper::Request<LimitedBody<hyper::Body>>) -> Result<http::Response<aws_smithy_http_server::body::BoxBody>, std::convert::Infallible> { todo!() }
+//! let app = tower::service_fn(handle);
+//! let app = limit_layer.layer(app);
+//! ```
+//!
+//! The inner service receives a [`LimitedBody`] rather than the original request body. The routes
+//! of a generated service expect a [`hyper::Body`], so apply [`RequestBodyLimitPlugin`] as an HTTP
+//! plugin to limit the request bodies of a generated service. The plugin also allows some
+//! operations larger or smaller bodies than others.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+
+use aws_smithy_types::byte_stream::ByteStream;
+use bytes::{Buf, Bytes};
+use http::{HeaderMap, Request, Response};
+use http_body::{Body, SizeHint};
+use pin_project_lite::pin_project;
+use thiserror::Error;
+use tower::{Layer, Service};
+
+use crate::body::BoxBody;
+use crate::error::BoxError;
+use crate::operation::OperationShape;
+use crate::plugin::{HttpMarker, Plugin};
+use crate::response::IntoResponse;
+use crate::runtime_error::PayloadTooLargeException;
+use crate::service::ServiceShape;
+use crate::shape_id::ShapeId;
+
+/// The error a limited request body yields once it has exceeded its limit.
+#[derive(Debug, Error)]
+#[error("request body is larger than the limit of {limit} bytes")]
+pub struct LengthLimitExceeded {
+    limit: usize,
+}
+
+pin_project! {
+    /// A request body that errors with [`LengthLimitExceeded`] once more than its limit has been
+    /// read from it.
+    ///
+    /// Trailers are passed through from the wrapped body.
+    pub struct LimitedBody<B> {
+        #[pin]
+        inner: B,
+        remaining: usize,
+        limit: usize,
+        // Set when the limit is exceeded, so the response can be replaced with a `413`.
+        tripped: Arc<AtomicBool>,
+    }
+}
+
+impl<B> Body for LimitedBody<B>
+where
+    B: Body,
+    B::Error: Into<BoxError>,
+{
+    type Data = B::Data;
+    type Error = BoxError;
+
+    fn poll_data(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.project();
+        let chunk = match ready!(this.inner.poll_data(cx)) {
+            Some(Ok(chunk)) => chunk,
+            Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+            None => return Poll::Ready(None),
+        };
+        if chunk.remaining() > *this.remaining {
+            this.tripped.store(true, Ordering::Relaxed);
+            return Poll::Ready(Some(Err(LengthLimitExceeded { limit: *this.limit }.into())));
+        }
+        *this.remaining -= chunk.remaining();
+        Poll::Ready(Some(Ok(chunk)))
+    }
+
+    fn poll_trailers(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        self.project().inner.poll_trailers(cx).map_err(Into::into)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl<B> From<LimitedBody<B>> for ByteStream
+where
+    B: Body<Data = Bytes> + Send + Sync + 'static,
+    B::Error: Into<BoxError>,
+{
+    fn from(body: LimitedBody<B>) -> Self {
+        ByteStream::from_body_0_4(body)
+    }
+}
+
+/// A [`tower::Layer`] used to apply [`RequestBodyLimit`].
+pub struct RequestBodyLimitLayer<P> {
+    limit: usize,
+    _protocol: PhantomData<P>,
+}
+
+impl<P> RequestBodyLimitLayer<P> {
+    /// Rejects request bodies larger than `limit` bytes.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<P> Clone for RequestBodyLimitLayer<P> {
+    fn clone(&self) -> Self {
+        Self::new(self.limit)
+    }
+}
+
+impl<P> fmt::Debug for RequestBodyLimitLayer<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestBodyLimitLayer")
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl<S, P> Layer<S> for RequestBodyLimitLayer<P> {
+    type Service = RequestBodyLimit<S, P>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestBodyLimit::new(inner, self.limit)
+    }
+}
+
+/// A middleware [`Service`] responsible for rejecting request bodies larger than a limit.
+pub struct RequestBodyLimit<S, P> {
+    inner: S,
+    limit: usize,
+    _protocol: PhantomData<P>,
+}
+
+impl<S, P> RequestBodyLimit<S, P> {
+    /// Wraps `inner`, rejecting request bodies larger than `limit` bytes.
+    pub fn new(inner: S, limit: usize) -> Self {
+        Self {
+            inner,
+            limit,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<S: Clone, P> Clone for RequestBodyLimit<S, P> {
+    fn clone(&self) -> Self {
+        Self::new(self.inner.clone(), self.limit)
+    }
+}
+
+impl<S: fmt::Debug, P> fmt::Debug for RequestBodyLimit<S, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestBodyLimit")
+            .field("inner", &self.inner)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+fn content_length<B>(req: &Request<B>) -> Option<usize> {
+    req.headers()
+        .get(http::header::CONTENT_LENGTH)?
+        .to_str()
+        .ok()?
+        .parse()
+        .ok()
+}
+
+impl<S, P, B> Service<Request<B>> for RequestBodyLimit<S, P>
+where
+    S: Service<Request<LimitedBody<B>>, Response = Response<BoxBody>>,
+    PayloadTooLargeException: IntoResponse<P>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = RequestBodyLimitFuture<S::Future, P>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let tripped = Arc::new(AtomicBool::new(false));
+        if matches!(content_length(&req), Some(length) if length > self.limit) {
+            return RequestBodyLimitFuture {
+                inner: None,
+                tripped,
+                _protocol: PhantomData,
+            };
+        }
+        let req = req.map(|inner| LimitedBody {
+            inner,
+            remaining: self.limit,
+            limit: self.limit,
+            tripped: tripped.clone(),
+        });
+
+        RequestBodyLimitFuture {
+            inner: Some(self.inner.call(req)),
+            tripped,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+pin_project! {
+    /// Future for [`RequestBodyLimit`].
+    pub struct RequestBodyLimitFuture<F, P> {
+        // `None` if the request was rejected without calling the inner service.
+        #[pin]
+        inner: Option<F>,
+        tripped: Arc<AtomicBool>,
+        _protocol: PhantomData<P>,
+    }
+}
+
+impl<F, P, E> Future for RequestBodyLimitFuture<F, P>
+where
+    F: Future<Output = Result<Response<BoxBody>, E>>,
+    PayloadTooLargeException: IntoResponse<P>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let payload_too_large = || Poll::Ready(Ok(IntoResponse::<P>::into_response(PayloadTooLargeException)));
+        let Some(inner) = this.inner.as_pin_mut() else {
+            return payload_too_large();
+        };
+        let res = ready!(inner.poll(cx));
+        if this.tripped.load(Ordering::Relaxed) {
+            return payload_too_large();
+        }
+        Poll::Ready(res)
+    }
+}
+
+/// A [`Plugin`] which applies [`RequestBodyLimit`] to every operation, with a limit that can be
+/// overridden for individual operations.
+///
+/// The `413` response is serialized according to the service's protocol.
+#[derive(Clone, Debug)]
+pub struct RequestBodyLimitPlugin {
+    default_limit: usize,
+    operation_limits: HashMap<ShapeId, usize>,
+}
+
+impl RequestBodyLimitPlugin {
+    /// Rejects request bodies larger than `default_limit` bytes, for operations without their own
+    /// limit.
+    pub fn new(default_limit: usize) -> Self {
+        Self {
+            default_limit,
+            operation_limits: HashMap::new(),
+        }
+    }
+
+    /// Rejects request bodies larger than `limit` bytes for the operation `operation`, instead of
+    /// the default limit.
+    pub fn operation_limit(mut self, operation: ShapeId, limit: usize) -> Self {
+        self.operation_limits.insert(operation, limit);
+        self
+    }
+}
+
+impl<Ser, Op, T> Plugin<Ser, Op, T> for RequestBodyLimitPlugin
+where
+    Ser: ServiceShape,
+    Op: OperationShape,
+{
+    type Output = RequestBodyLimit<T, Ser::Protocol>;
+
+    fn apply(&self, input: T) -> Self::Output {
+        let limit = self
+            .operation_limits
+            .get(&Op::ID)
+            .copied()
+            .unwrap_or(self.default_limit);
+        RequestBodyLimit::new(input, limit)
+    }
+}
+
+impl HttpMarker for RequestBodyLimitPlugin {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extension::RuntimeErrorExtension;
+    use crate::protocol::rest_json_1::RestJson1;
+    use crate::protocol::rpc_v2::RpcV2;
+    use http::{HeaderValue, StatusCode};
+    use hyper::body::HttpBody;
+    use std::convert::Infallible;
+    use tower::{service_fn, ServiceExt};
+
+    type Body = hyper::Body;
+
+    // Buffers the request body, and responds with a `400` if that fails.
+    async fn buffer(req: Request<LimitedBody<Body>>) -> Result<Response<BoxBody>, Infallible> {
+        let status = match hyper::body::to_bytes(req.into_body()).await {
+            Ok(_) => StatusCode::OK,
+            Err(_) => StatusCode::BAD_REQUEST,
+        };
+        Ok(Response::builder().status(status).body(crate::body::empty()).unwrap())
+    }
+
+    fn streaming_body(chunks: &[&'static [u8]]) -> Body {
+        let chunks: Vec<Result<_, Infallible>> = chunks.iter().map(|chunk| Ok(Bytes::from_static(chunk))).collect();
+        Body::wrap_stream(futures_util::stream::iter(chunks))
+    }
+
+    async fn status(limit: usize, req: Request<Body>) -> StatusCode {
+        let svc = RequestBodyLimitLayer::<RestJson1>::new(limit).layer(service_fn(buffer));
+        svc.oneshot(req).await.unwrap().status()
+    }
+
+    fn request(body: Body, content_length: Option<usize>) -> Request<Body> {
+        let mut req = Request::post("/");
+        if let Some(length) = content_length {
+            req = req.header(http::header::CONTENT_LENGTH, length);
+        }
+        req.body(body).unwrap()
+    }
+
+    #[tokio::test]
+    async fn under_limit() {
+        assert_eq!(
+            status(8, request(Body::from("12345678"), Some(8))).await,
+            StatusCode::OK
+        );
+        let streaming = streaming_body(&[b"1234", b"5678"]);
+        assert_eq!(status(8, request(streaming, None)).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn over_limit_content_length() {
+        let called = Arc::new(AtomicBool::new(false));
+        let inner = {
+            let called = called.clone();
+            service_fn(move |req| {
+                called.store(true, Ordering::Relaxed);
+                buffer(req)
+            })
+        };
+        let svc = RequestBodyLimitLayer::<RestJson1>::new(7).layer(inner);
+        let res = svc.oneshot(request(Body::from("12345678"), Some(8))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(res.headers()["X-Amzn-Errortype"], "PayloadTooLargeException");
+        assert_eq!(
+            res.extensions().get::<RuntimeErrorExtension>().map(|ext| ext.as_str()),
+            Some("PayloadTooLargeException")
+        );
+        assert!(!called.load(Ordering::Relaxed), "inner service must not be called");
+    }
+
+    #[tokio::test]
+    async fn payload_too_large_is_serialized_per_protocol() {
+        let svc = RequestBodyLimitLayer::<RpcV2>::new(7).layer(service_fn(buffer));
+        let streaming = streaming_body(&[b"1234", b"5678"]);
+        let res = svc.oneshot(request(streaming, None)).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(res.headers()["smithy-protocol"], "rpc-v2-cbor");
+    }
+
+    #[tokio::test]
+    async fn over_limit_mid_stream() {
+        let streaming = streaming_body(&[b"1234", b"5678"]);
+        assert_eq!(status(7, request(streaming, None)).await, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    #[tokio::test]
+    async fn trailers_are_forwarded() {
+        let (mut sender, body) = Body::channel();
+        tokio::spawn(async move {
+            sender.send_data(Bytes::from_static(b"1234")).await.unwrap();
+            let mut trailers = HeaderMap::new();
+            trailers.insert("x-amz-checksum-crc32", HeaderValue::from_static("m3kOGw=="));
+            sender.send_trailers(trailers).await.unwrap();
+        });
+
+        let svc = RequestBodyLimitLayer::<RestJson1>::new(8).layer(service_fn(
+            |req: Request<LimitedBody<Body>>| async move {
+                let mut body = req.into_body();
+                while let Some(chunk) = body.data().await {
+                    chunk.unwrap();
+                }
+                let trailers = body.trailers().await.unwrap().expect("trailers are forwarded");
+                assert_eq!(trailers["x-amz-checksum-crc32"], "m3kOGw==");
+                Ok::<_, Infallible>(Response::new(crate::body::empty()))
+            },
+        ));
+        let res = svc.oneshot(request(body, None)).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn operation_limit_overrides_default() {
+        struct Service;
+        impl ServiceShape for Service {
+            const ID: ShapeId = ShapeId::new("com.example#Service", "com.example", "Service");
+            const VERSION: Option<&'static str> = None;
+            type Protocol = RestJson1;
+            type Operations = ();
+        }
+        struct Small;
+        impl OperationShape for Small {
+            const ID: ShapeId = ShapeId::new("com.example#Small", "com.example", "Small");
+            type Input = ();
+            type Output = ();
+            type Error = Infallible;
+        }
+        struct Large;
+        impl OperationShape for Large {
+            const ID: ShapeId = ShapeId::new("com.example#Large", "com.example", "Large");
+            type Input = ();
+            type Output = ();
+            type Error = Infallible;
+        }
+
+        let plugin = RequestBodyLimitPlugin::new(4).operation_limit(Large::ID, 8);
+        let small = Plugin::<Service, Small, _>::apply(&plugin, service_fn(buffer));
+        let large = Plugin::<Service, Large, _>::apply(&plugin, service_fn(buffer));
+
+        let res = small.oneshot(request(Body::from("12345678"), Some(8))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let res = large.oneshot(request(Body::from("12345678"), Some(8))).await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
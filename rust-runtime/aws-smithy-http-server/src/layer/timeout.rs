@@ -0,0 +1,260 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Middleware for cancelling requests that take too long to handle.
+//!
+//! When the inner service does not respond within the configured duration, its future is dropped
+//! and a [`TimeoutException`] is returned instead, serialized according to the protocol `P`. This
+//! results in a `504 Gateway Timeout` in every protocol.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use aws_smithy_http_server::layer::timeout::TimeoutLayer;
+//! use aws_smithy_http_server::protocol::rest_json_1::RestJson1;
+//! use std::time::Duration;
+//! use tower::Layer;
+//!
+//! // Give up on requests that take longer than 30 seconds.
+//! let timeout_layer = TimeoutLayer::<RestJson1>::new(Duration::from_secs(30));
+//! # async fn handle(_req: hyper::Request<hyper::Body>) -> Result<http::Response<aws_smithy_http_server::body::BoxBody>, std::convert::Infallible> { todo!() }
+//! let app = tower::service_fn(handle);
+//! let app = timeout_layer.layer(app);
+//! ```
+//!
+//! To give some operations more or less time than others, apply [`TimeoutPlugin`] as an HTTP
+//! plugin instead of applying the layer around the router.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
+
+use http::{Request, Response};
+use pin_project_lite::pin_project;
+use tower::{Layer, Service};
+
+use crate::body::BoxBody;
+use crate::operation::OperationShape;
+use crate::plugin::{HttpMarker, Plugin};
+use crate::response::IntoResponse;
+use crate::runtime_error::TimeoutException;
+use crate::service::ServiceShape;
+use crate::shape_id::ShapeId;
+
+/// A [`tower::Layer`] used to apply [`Timeout`].
+pub struct TimeoutLayer<P> {
+    duration: Duration,
+    _protocol: PhantomData<P>,
+}
+
+impl<P> TimeoutLayer<P> {
+    /// Cancels requests that take longer than `duration` to handle.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<P> Clone for TimeoutLayer<P> {
+    fn clone(&self) -> Self {
+        Self::new(self.duration)
+    }
+}
+
+impl<P> fmt::Debug for TimeoutLayer<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TimeoutLayer")
+            .field("duration", &self.duration)
+            .finish()
+    }
+}
+
+impl<S, P> Layer<S> for TimeoutLayer<P> {
+    type Service = Timeout<S, P>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Timeout::new(inner, self.duration)
+    }
+}
+
+/// A middleware [`Service`] responsible for cancelling requests that take too long to handle.
+pub struct Timeout<S, P> {
+    inner: S,
+    duration: Duration,
+    _protocol: PhantomData<P>,
+}
+
+impl<S, P> Timeout<S, P> {
+    /// Wraps `inner`, cancelling requests that take longer than `duration` to handle.
+    pub fn new(inner: S, duration: Duration) -> Self {
+        Self {
+            inner,
+            duration,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<S: Clone, P> Clone for Timeout<S, P> {
+    fn clone(&self) -> Self {
+        Self::new(self.inner.clone(), self.duration)
+    }
+}
+
+impl<S: fmt::Debug, P> fmt::Debug for Timeout<S, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timeout")
+            .field("inner", &self.inner)
+            .field("duration", &self.duration)
+            .finish()
+    }
+}
+
+impl<S, P, B> Service<Request<B>> for Timeout<S, P>
+where
+    S: Service<Request<B>, Response = Response<BoxBody>>,
+    TimeoutException: IntoResponse<P>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TimeoutFuture<S::Future, P>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        TimeoutFuture {
+            inner: tokio::time::timeout(self.duration, self.inner.call(req)),
+            _protocol: PhantomData,
+        }
+    }
+}
+
+pin_project! {
+    /// Future for [`Timeout`].
+    pub struct TimeoutFuture<F, P> {
+        #[pin]
+        inner: tokio::time::Timeout<F>,
+        _protocol: PhantomData<P>,
+    }
+}
+
+impl<F, P, E> Future for TimeoutFuture<F, P>
+where
+    F: Future<Output = Result<Response<BoxBody>, E>>,
+    TimeoutException: IntoResponse<P>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match ready!(self.project().inner.poll(cx)) {
+            Ok(res) => Poll::Ready(res),
+            Err(_elapsed) => Poll::Ready(Ok(IntoResponse::<P>::into_response(TimeoutException))),
+        }
+    }
+}
+
+/// A [`Plugin`] which applies [`Timeout`] to every operation, with a duration that can be
+/// overridden for individual operations.
+///
+/// The timeout response is serialized according to the service's protocol.
+#[derive(Clone, Debug)]
+pub struct TimeoutPlugin {
+    default_duration: Duration,
+    operation_durations: HashMap<ShapeId, Duration>,
+}
+
+impl TimeoutPlugin {
+    /// Cancels requests that take longer than `default_duration` to handle, for operations without
+    /// their own duration.
+    pub fn new(default_duration: Duration) -> Self {
+        Self {
+            default_duration,
+            operation_durations: HashMap::new(),
+        }
+    }
+
+    /// Cancels requests to the operation `operation` that take longer than `duration` to handle,
+    /// instead of the default duration.
+    pub fn operation_timeout(mut self, operation: ShapeId, duration: Duration) -> Self {
+        self.operation_durations.insert(operation, duration);
+        self
+    }
+}
+
+impl<Ser, Op, T> Plugin<Ser, Op, T> for TimeoutPlugin
+where
+    Ser: ServiceShape,
+    Op: OperationShape,
+{
+    type Output = Timeout<T, Ser::Protocol>;
+
+    fn apply(&self, input: T) -> Self::Output {
+        let duration = self
+            .operation_durations
+            .get(&Op::ID)
+            .copied()
+            .unwrap_or(self.default_duration);
+        Timeout::new(input, duration)
+    }
+}
+
+impl HttpMarker for TimeoutPlugin {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extension::RuntimeErrorExtension;
+    use crate::protocol::rest_json_1::RestJson1;
+    use crate::protocol::rpc_v2::RpcV2;
+    use http::StatusCode;
+    use std::convert::Infallible;
+    use tower::{service_fn, ServiceExt};
+
+    async fn handle(delay: Duration) -> Result<Response<BoxBody>, Infallible> {
+        tokio::time::sleep(delay).await;
+        Ok(Response::new(crate::body::empty()))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_handler_times_out() {
+        let svc = TimeoutLayer::<RestJson1>::new(Duration::from_millis(10))
+            .layer(service_fn(|_req: Request<()>| handle(Duration::from_secs(10))));
+        let res = svc.oneshot(Request::new(())).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(res.headers()["X-Amzn-Errortype"], "TimeoutException");
+        assert_eq!(
+            res.extensions().get::<RuntimeErrorExtension>().map(|ext| ext.as_str()),
+            Some("TimeoutException")
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn timeout_is_serialized_per_protocol() {
+        let svc = TimeoutLayer::<RpcV2>::new(Duration::from_millis(10))
+            .layer(service_fn(|_req: Request<()>| handle(Duration::from_secs(10))));
+        let res = svc.oneshot(Request::new(())).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(res.headers()["smithy-protocol"], "rpc-v2-cbor");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn fast_handler_passes_through() {
+        let svc = TimeoutLayer::<RestJson1>::new(Duration::from_secs(10))
+            .layer(service_fn(|_req: Request<()>| handle(Duration::ZERO)));
+        let res = svc.oneshot(Request::new(())).await.unwrap();
+
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
@@ -8,7 +8,10 @@ use crate::extension::RuntimeErrorExtension;
 use crate::protocol::ec2_query::router::Error;
 use crate::protocol::ec2_query::Ec2Query;
 use crate::response::{IntoResponse, Response};
-use crate::runtime_error::{InternalFailureException, INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE};
+use crate::runtime_error::{
+    InternalFailureException, PayloadTooLargeException, TimeoutException,
+    INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE,
+};
 use http::StatusCode;
 
 use super::rejection::{RequestRejection, ResponseRejection};
@@ -19,6 +22,8 @@ pub enum RuntimeError {
     InternalFailure(crate::Error),
     NotAcceptable,
     UnsupportedMediaType,
+    Timeout,
+    PayloadTooLarge,
     Validation(String),
 }
 
@@ -29,6 +34,8 @@ impl RuntimeError {
             Self::InternalFailure(_) => "InternalFailureException",
             Self::NotAcceptable => "NotAcceptableException",
             Self::UnsupportedMediaType => "UnsupportedMediaTypeException",
+            Self::Timeout => "TimeoutException",
+            Self::PayloadTooLarge => "PayloadTooLargeException",
             Self::Validation(_) => "ValidationException",
         }
     }
@@ -39,6 +46,8 @@ impl RuntimeError {
             Self::InternalFailure(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::NotAcceptable => StatusCode::NOT_ACCEPTABLE,
             Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
             Self::Validation(_) => StatusCode::BAD_REQUEST,
         }
     }
@@ -55,6 +64,18 @@ impl IntoResponse<Ec2Query> for InternalFailureException {
         IntoResponse::<Ec2Query>::into_response(RuntimeError::InternalFailure(crate::Error::new(String::new())))
     }
 }
+
+impl IntoResponse<Ec2Query> for TimeoutException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<Ec2Query>::into_response(RuntimeError::Timeout)
+    }
+}
+
+impl IntoResponse<Ec2Query> for PayloadTooLargeException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<Ec2Query>::into_response(RuntimeError::PayloadTooLarge)
+    }
+}
 //
 // impl IntoResponse<AwsJson1_1> for InternalFailureException {
 //     fn into_response(self) -> http::Response<crate::body::BoxBody> {
@@ -10,6 +10,7 @@ pub mod ec2_query;
 pub mod rest;
 pub mod rest_json_1;
 pub mod rest_xml;
+pub mod rpc_v2;
 
 use crate::rejection::MissingContentTypeReason;
 use aws_smithy_runtime_api::http::Headers as SmithyHeaders;
@@ -35,6 +35,8 @@ use super::RestJson1;
 use crate::extension::RuntimeErrorExtension;
 use crate::response::IntoResponse;
 use crate::runtime_error::InternalFailureException;
+use crate::runtime_error::PayloadTooLargeException;
+use crate::runtime_error::TimeoutException;
 use crate::runtime_error::INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE;
 use http::StatusCode;
 
@@ -53,6 +55,10 @@ pub enum RuntimeError {
     NotAcceptable,
     /// The request does not contain the expected `Content-Type` header value.
     UnsupportedMediaType,
+    /// The operation handler did not complete within its configured timeout.
+    Timeout,
+    /// The request body is larger than the server is willing to buffer.
+    PayloadTooLarge,
     /// Operation input contains data that does not adhere to the modeled [constraint traits].
     /// [constraint traits]: <https://awslabs.github.io/smithy/2.0/spec/constraint-traits.html>
     Validation(String),
@@ -68,6 +74,8 @@ impl RuntimeError {
             Self::InternalFailure(_) => "InternalFailureException",
             Self::NotAcceptable => "NotAcceptableException",
             Self::UnsupportedMediaType => "UnsupportedMediaTypeException",
+            Self::Timeout => "TimeoutException",
+            Self::PayloadTooLarge => "PayloadTooLargeException",
             Self::Validation(_) => "ValidationException",
         }
     }
@@ -78,6 +86,8 @@ impl RuntimeError {
             Self::InternalFailure(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Self::NotAcceptable => StatusCode::NOT_ACCEPTABLE,
             Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
             Self::Validation(_) => StatusCode::BAD_REQUEST,
         }
     }
@@ -89,6 +99,18 @@ impl IntoResponse<RestJson1> for InternalFailureException {
     }
 }
 
+impl IntoResponse<RestJson1> for TimeoutException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<RestJson1>::into_response(RuntimeError::Timeout)
+    }
+}
+
+impl IntoResponse<RestJson1> for PayloadTooLargeException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<RestJson1>::into_response(RuntimeError::PayloadTooLarge)
+    }
+}
+
 impl IntoResponse<RestJson1> for RuntimeError {
     fn into_response(self) -> http::Response<crate::body::BoxBody> {
         let res = http::Response::builder()
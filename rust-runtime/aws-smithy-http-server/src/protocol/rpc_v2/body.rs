@@ -0,0 +1,161 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Buffering of RPC v2 request bodies.
+//!
+//! RPC v2 operations with non-streaming input are sent as a single CBOR document, which has to be
+//! buffered in full before it can be deserialized. [`buffer_request_body`] does so while checking
+//! that the request is framed unambiguously, and while capping the number of bytes it is willing
+//! to hold in memory.
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use http::header::{CONTENT_LENGTH, TRANSFER_ENCODING};
+use http::{HeaderMap, Version};
+
+use crate::body::HttpBody;
+
+use super::rejection::RequestRejection;
+
+/// The default cap on the size of a buffered request body, in bytes.
+pub const DEFAULT_BODY_LIMIT: usize = 2 * 1024 * 1024;
+
+/// Reads a request's `Content-Length`, rejecting requests whose framing is ambiguous.
+///
+/// A request may be framed by `Content-Length` or, over HTTP/1.1, by `Transfer-Encoding`, but
+/// not by both. `Transfer-Encoding` is not allowed at all over HTTP/2, which has its own framing.
+fn content_length(version: Version, headers: &HeaderMap) -> Result<Option<usize>, &'static str> {
+    let transfer_encoding = headers.contains_key(TRANSFER_ENCODING);
+    if transfer_encoding && version >= Version::HTTP_2 {
+        return Err("`Transfer-Encoding` is not allowed over HTTP/2");
+    }
+
+    let mut values = headers.get_all(CONTENT_LENGTH).into_iter();
+    let Some(value) = values.next() else {
+        return Ok(None);
+    };
+    if transfer_encoding {
+        return Err("request has both `Content-Length` and `Transfer-Encoding` headers");
+    }
+    if values.any(|other| other != value) {
+        return Err("request has conflicting `Content-Length` headers");
+    }
+    value
+        .to_str()
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .map(Some)
+        .ok_or("`Content-Length` is not a valid length")
+}
+
+/// Buffers the body of an RPC v2 request.
+///
+/// Requests that declare a `Content-Length` larger than `limit` are rejected before any of the body
+/// is read. Requests without one are read until they exceed `limit`, at which point reading stops and
+/// the request is rejected, so that at most `limit` bytes are ever buffered.
+pub async fn buffer_request_body<B>(request: http::Request<B>, limit: usize) -> Result<Bytes, RequestRejection>
+where
+    B: HttpBody + Unpin,
+    B::Error: Into<crate::error::BoxError>,
+{
+    let (parts, mut body) = request.into_parts();
+    let content_length = content_length(parts.version, &parts.headers).map_err(RequestRejection::InvalidFraming)?;
+    if content_length.is_some_and(|length| length > limit) {
+        return Err(RequestRejection::BodyTooLarge { limit });
+    }
+
+    let mut buffer = BytesMut::with_capacity(content_length.unwrap_or_default());
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk.map_err(|err| RequestRejection::BufferHttpBodyBytes(crate::Error::new(err)))?;
+        if buffer.len() + chunk.remaining() > limit {
+            return Err(RequestRejection::BodyTooLarge { limit });
+        }
+        buffer.put(chunk);
+    }
+
+    if content_length.is_some_and(|length| length != buffer.len()) {
+        return Err(RequestRejection::InvalidFraming(
+            "body length does not match `Content-Length`",
+        ));
+    }
+    Ok(buffer.freeze())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::protocol::rpc_v2::runtime_error::RuntimeError;
+    use crate::protocol::rpc_v2::RpcV2;
+    use crate::response::IntoResponse;
+    use http::StatusCode;
+
+    const BODY: &[u8] = b"\xa1\x63foo\x63bar";
+
+    fn request(body: hyper::Body, headers: &[(&'static str, &str)]) -> http::Request<hyper::Body> {
+        let mut request = http::Request::post("/service/Service/operation/Operation");
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+        request.body(body).unwrap()
+    }
+
+    fn streaming_body(chunks: &[&'static [u8]]) -> hyper::Body {
+        let chunks: Vec<Result<_, std::io::Error>> = chunks.iter().map(|chunk| Ok(Bytes::from_static(chunk))).collect();
+        hyper::Body::wrap_stream(futures_util::stream::iter(chunks))
+    }
+
+    #[tokio::test]
+    async fn well_formed_body() {
+        let content_length = BODY.len().to_string();
+        let req = request(BODY.into(), &[("content-length", &content_length)]);
+        assert_eq!(buffer_request_body(req, BODY.len()).await.unwrap(), BODY);
+
+        let req = request(
+            streaming_body(&[&BODY[..3], &BODY[3..]]),
+            &[("transfer-encoding", "chunked")],
+        );
+        assert_eq!(buffer_request_body(req, DEFAULT_BODY_LIMIT).await.unwrap(), BODY);
+    }
+
+    #[tokio::test]
+    async fn oversized_body() {
+        let content_length = BODY.len().to_string();
+        let req = request(BODY.into(), &[("content-length", &content_length)]);
+        let rejection = buffer_request_body(req, BODY.len() - 1).await.unwrap_err();
+        assert!(matches!(rejection, RequestRejection::BodyTooLarge { limit } if limit == BODY.len() - 1));
+        let res = IntoResponse::<RpcV2>::into_response(RuntimeError::from(rejection));
+        assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // Without a `Content-Length`, the limit is only hit while reading the body.
+        let req = request(streaming_body(&[&BODY[..3], &BODY[3..]]), &[]);
+        let rejection = buffer_request_body(req, 4).await.unwrap_err();
+        assert!(matches!(rejection, RequestRejection::BodyTooLarge { limit: 4 }));
+    }
+
+    #[tokio::test]
+    async fn ambiguous_framing() {
+        let content_length = BODY.len().to_string();
+        for headers in [
+            vec![
+                ("content-length", content_length.as_str()),
+                ("transfer-encoding", "chunked"),
+            ],
+            vec![("content-length", content_length.as_str()), ("content-length", "1")],
+            vec![("content-length", "-1")],
+            vec![("content-length", "1")],
+        ] {
+            let req = request(BODY.into(), &headers);
+            let rejection = buffer_request_body(req, DEFAULT_BODY_LIMIT).await.unwrap_err();
+            assert!(
+                matches!(rejection, RequestRejection::InvalidFraming(_)),
+                "headers: {headers:?}"
+            );
+        }
+
+        let mut req = request(BODY.into(), &[("transfer-encoding", "chunked")]);
+        *req.version_mut() = Version::HTTP_2;
+        let rejection = buffer_request_body(req, DEFAULT_BODY_LIMIT).await.unwrap_err();
+        assert!(matches!(rejection, RequestRejection::InvalidFraming(_)));
+    }
+}
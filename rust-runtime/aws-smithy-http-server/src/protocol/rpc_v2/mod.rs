@@ -0,0 +1,115 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+pub mod body;
+pub mod rejection;
+pub mod router;
+pub mod runtime_error;
+
+use http::HeaderMap;
+
+use rejection::RequestRejection;
+
+/// [Smithy RPC v2 Protocol](https://smithy.io/2.0/additional-specs/protocols/smithy-rpc-v2.html).
+pub struct RpcV2;
+
+/// The header used by clients and servers to name the protocol a message is serialized with.
+pub const SMITHY_PROTOCOL_HEADER: &str = "smithy-protocol";
+
+/// The value of the [`SMITHY_PROTOCOL_HEADER`] for the CBOR flavor of RPC v2.
+pub const SMITHY_PROTOCOL_VALUE: &str = "rpc-v2-cbor";
+
+/// Checks that a request's headers ask for RPC v2 CBOR.
+///
+/// The request must carry a `smithy-protocol: rpc-v2-cbor` header, and if it has an `Accept`
+/// header, that header must allow for `application/cbor` responses.
+#[allow(clippy::result_large_err)]
+pub fn check_protocol_headers(headers: &HeaderMap) -> Result<(), RequestRejection> {
+    let protocol = headers
+        .get(SMITHY_PROTOCOL_HEADER)
+        .ok_or(RequestRejection::MissingSmithyProtocolHeader)?;
+    if protocol != SMITHY_PROTOCOL_VALUE {
+        return Err(RequestRejection::UnsupportedSmithyProtocol(
+            String::from_utf8_lossy(protocol.as_bytes()).into_owned(),
+        ));
+    }
+
+    let cbor = "application/cbor".parse().expect("MIME type is valid");
+    if !crate::protocol::accept_header_classifier(headers, &cbor) {
+        return Err(RequestRejection::NotAcceptable);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::runtime_error::RuntimeError;
+    use super::*;
+    use crate::response::IntoResponse;
+    use http::{HeaderValue, StatusCode};
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.parse().unwrap(), HeaderValue::from_static(value)))
+            .collect()
+    }
+
+    fn status_code(rejection: RequestRejection) -> StatusCode {
+        IntoResponse::<RpcV2>::into_response(RuntimeError::from(rejection)).status()
+    }
+
+    #[test]
+    fn accepts_rpc_v2_cbor() {
+        check_protocol_headers(&headers(&[("smithy-protocol", "rpc-v2-cbor")])).unwrap();
+        check_protocol_headers(&headers(&[
+            ("smithy-protocol", "rpc-v2-cbor"),
+            ("accept", "application/cbor"),
+        ]))
+        .unwrap();
+        check_protocol_headers(&headers(&[("smithy-protocol", "rpc-v2-cbor"), ("accept", "*/*")])).unwrap();
+    }
+
+    #[test]
+    fn rejects_missing_smithy_protocol_header() {
+        let rejection = check_protocol_headers(&headers(&[("accept", "application/cbor")])).unwrap_err();
+        assert!(matches!(rejection, RequestRejection::MissingSmithyProtocolHeader));
+        assert_eq!(status_code(rejection), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn rejects_unsupported_smithy_protocol() {
+        let rejection = check_protocol_headers(&headers(&[("smithy-protocol", "rpc-v2-json")])).unwrap_err();
+        assert!(matches!(
+            &rejection,
+            RequestRejection::UnsupportedSmithyProtocol(protocol) if protocol == "rpc-v2-json"
+        ));
+        assert_eq!(status_code(rejection), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn rejects_unsupported_accept() {
+        let rejection = check_protocol_headers(&headers(&[
+            ("smithy-protocol", "rpc-v2-cbor"),
+            ("accept", "application/json"),
+        ]))
+        .unwrap_err();
+        assert!(matches!(rejection, RequestRejection::NotAcceptable));
+        assert_eq!(status_code(rejection), StatusCode::NOT_ACCEPTABLE);
+    }
+
+    #[test]
+    fn runtime_errors_name_the_protocol() {
+        let res =
+            IntoResponse::<RpcV2>::into_response(RuntimeError::from(RequestRejection::MissingSmithyProtocolHeader));
+        assert_eq!(res.headers()[SMITHY_PROTOCOL_HEADER], SMITHY_PROTOCOL_VALUE);
+        assert_eq!(
+            res.extensions()
+                .get::<crate::extension::RuntimeErrorExtension>()
+                .map(|ext| ext.as_str()),
+            Some("InvalidSmithyProtocolException")
+        );
+    }
+}
@@ -0,0 +1,59 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Rejection types for the [`crate::protocol::rpc_v2`] protocol.
+//!
+//! See [`crate::protocol::rest_json_1::rejection`] for an overview of rejection types.
+
+use crate::rejection::MissingContentTypeReason;
+use aws_smithy_runtime_api::http::HttpError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ResponseRejection {
+    #[error("error serializing CBOR-encoded body: {0}")]
+    Serialization(#[from] aws_smithy_types::error::operation::SerializationError),
+    #[error("error building HTTP response: {0}")]
+    HttpBuild(#[from] http::Error),
+}
+
+#[derive(Debug, Error)]
+pub enum RequestRejection {
+    #[error("error converting non-streaming body to bytes: {0}")]
+    BufferHttpBodyBytes(crate::Error),
+    /// Used when the request does not contain a `smithy-protocol` header.
+    #[error("expected `smithy-protocol` header not found")]
+    MissingSmithyProtocolHeader,
+    /// Used when the `smithy-protocol` header is present, but its value is not a protocol this
+    /// server supports.
+    #[error("unsupported value for `smithy-protocol` header: {0}")]
+    UnsupportedSmithyProtocol(String),
+    /// Used when the request's framing is ambiguous or inconsistent, like when it has both
+    /// `Content-Length` and `Transfer-Encoding` headers.
+    #[error("request has invalid framing: {0}")]
+    InvalidFraming(&'static str),
+    /// Used when the request body is larger than the configured limit.
+    #[error("request body is larger than the limit of {limit} bytes")]
+    BodyTooLarge { limit: usize },
+    #[error("request contains invalid value for `Accept` header")]
+    NotAcceptable,
+    #[error("expected `Content-Type` header not found: {0}")]
+    MissingContentType(#[from] MissingContentTypeReason),
+    #[error("request does not adhere to modeled constraints: {0}")]
+    ConstraintViolation(String),
+
+    /// Typically happens when the request has headers that are not valid UTF-8.
+    #[error("failed to convert request: {0}")]
+    HttpConversion(#[from] HttpError),
+}
+
+impl From<std::convert::Infallible> for RequestRejection {
+    fn from(_err: std::convert::Infallible) -> Self {
+        match _err {}
+    }
+}
+
+convert_to_request_rejection!(hyper::Error, BufferHttpBodyBytes);
+convert_to_request_rejection!(Box<dyn std::error::Error + Send + Sync + 'static>, BufferHttpBodyBytes);
@@ -0,0 +1,303 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::convert::Infallible;
+
+use tower::Layer;
+use tower::Service;
+
+use crate::body::{empty, BoxBody};
+use crate::extension::RuntimeErrorExtension;
+use crate::response::IntoResponse;
+use crate::routing::tiny_map::TinyMap;
+use crate::routing::{method_disallowed, Route, Router, UNKNOWN_OPERATION_EXCEPTION};
+
+use super::{RpcV2, SMITHY_PROTOCOL_HEADER, SMITHY_PROTOCOL_VALUE};
+
+use thiserror::Error;
+
+/// An RPC v2 routing error.
+#[derive(Debug, Error)]
+pub enum Error {
+    /// Method was not `POST`.
+    #[error("method not POST")]
+    MethodNotAllowed,
+    /// The request path is not of the form `/service/{Service}/operation/{Operation}`.
+    #[error("request path is not of the form `/service/{{Service}}/operation/{{Operation}}`")]
+    InvalidPath,
+    /// Operation not found.
+    #[error("operation not found")]
+    NotFound,
+}
+
+// This constant determines when the `TinyMap` implementation switches from being a `Vec` to a
+// `HashMap`. This is chosen to be 15 as a result of the discussion around
+// https://github.com/smithy-lang/smithy-rs/pull/1429#issuecomment-1147516546
+const ROUTE_CUTOFF: usize = 15;
+
+/// A [`Router`] supporting the [`Smithy RPC v2`] protocol.
+///
+/// Routes are keyed by `{Service}.{Operation}`, using the shape names of the service and the
+/// operation without their namespaces.
+///
+/// Like the other protocol routers, it is served by wrapping it in a
+/// [`RoutingService`](crate::routing::RoutingService), which implements [`tower::Service`] and so
+/// composes with any tower middleware. Routing errors are then serialized as RPC v2 responses.
+/// Cloning the router clones its routes, which are typically cheaply cloneable [`Route`]s.
+///
+/// [Smithy RPC v2]: https://smithy.io/2.0/additional-specs/protocols/smithy-rpc-v2.html
+#[derive(Debug, Clone)]
+pub struct RpcV2Router<S> {
+    routes: TinyMap<String, S, ROUTE_CUTOFF>,
+}
+
+impl<S> RpcV2Router<S> {
+    /// Applies a [`Layer`] uniformly to all routes.
+    pub fn layer<L>(self, layer: L) -> RpcV2Router<L::Service>
+    where
+        L: Layer<S>,
+    {
+        RpcV2Router {
+            routes: self
+                .routes
+                .into_iter()
+                .map(|(key, route)| (key, layer.layer(route)))
+                .collect(),
+        }
+    }
+
+    /// Applies type erasure to the inner route using [`Route::new`].
+    pub fn boxed<B>(self) -> RpcV2Router<Route<B>>
+    where
+        S: Service<http::Request<B>, Response = http::Response<BoxBody>, Error = Infallible>,
+        S: Send + Clone + 'static,
+        S::Future: Send + 'static,
+    {
+        RpcV2Router {
+            routes: self.routes.into_iter().map(|(key, s)| (key, Route::new(s))).collect(),
+        }
+    }
+}
+
+/// Extracts the route key `{Service}.{Operation}` from a request path.
+///
+/// The path must end in `/service/{Service}/operation/{Operation}`, but may be preceded by a
+/// prefix. `{Service}` may be qualified with its namespace, which is not part of the route key.
+fn route_key(path: &str) -> Option<String> {
+    let mut segments = path.rsplit('/');
+    let operation = segments.next()?;
+    if segments.next()? != "operation" {
+        return None;
+    }
+    let service = segments.next()?;
+    if segments.next()? != "service" {
+        return None;
+    }
+    let service = service.rsplit('.').next()?;
+
+    let is_shape_name = |name: &str| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_shape_name(service) || !is_shape_name(operation) {
+        return None;
+    }
+    Some(format!("{service}.{operation}"))
+}
+
+impl<B, S> Router<B> for RpcV2Router<S>
+where
+    S: Clone,
+{
+    type Service = S;
+    type Error = Error;
+
+    async fn match_route(&self, request: &mut http::Request<B>) -> Result<S, Self::Error> {
+        // Only `Method::POST` is allowed.
+        if request.method() != http::Method::POST {
+            return Err(Error::MethodNotAllowed);
+        }
+
+        let key = route_key(request.uri().path()).ok_or(Error::InvalidPath)?;
+
+        // Lookup in the `TinyMap` for a route for the key.
+        let route = self.routes.get(key.as_str()).ok_or(Error::NotFound)?;
+        Ok(route.clone())
+    }
+}
+
+impl<S> FromIterator<(String, S)> for RpcV2Router<S> {
+    #[inline]
+    fn from_iter<T: IntoIterator<Item = (String, S)>>(iter: T) -> Self {
+        Self {
+            routes: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl IntoResponse<RpcV2> for Error {
+    fn into_response(self) -> http::Response<BoxBody> {
+        match self {
+            Error::MethodNotAllowed => method_disallowed(),
+            _ => http::Response::builder()
+                .status(http::StatusCode::NOT_FOUND)
+                .header(SMITHY_PROTOCOL_HEADER, SMITHY_PROTOCOL_VALUE)
+                .extension(RuntimeErrorExtension::new(
+                    UNKNOWN_OPERATION_EXCEPTION.to_string(),
+                ))
+                .body(empty())
+                .expect("invalid HTTP response for RPC v2 routing error; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{protocol::test_helpers::req, routing::Router};
+
+    use http::Method;
+    use pretty_assertions::assert_eq;
+
+    fn router() -> RpcV2Router<&'static str> {
+        ["Service.Operation", "Service.OtherOperation"]
+            .into_iter()
+            .map(|key| (key.to_string(), key))
+            .collect()
+    }
+
+    #[test]
+    fn route_key_from_path() {
+        assert_eq!(
+            route_key("/service/Service/operation/Operation").as_deref(),
+            Some("Service.Operation")
+        );
+        assert_eq!(
+            route_key("/prefix/service/Service/operation/Operation").as_deref(),
+            Some("Service.Operation")
+        );
+        assert_eq!(
+            route_key("/service/com.example.Service/operation/Operation").as_deref(),
+            Some("Service.Operation")
+        );
+
+        for path in [
+            "/",
+            "/service/Service",
+            "/service/Service/operation",
+            "/service/Service/operation/",
+            "/service//operation/Operation",
+            "/services/Service/operation/Operation",
+            "/service/Service/operations/Operation",
+            "/service/Service/operation/Operation/",
+            "/service/Service/operation/Oper-ation",
+        ] {
+            assert_eq!(route_key(path), None, "path: {path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn matched_operation() {
+        let router = router();
+
+        for (uri, expected) in [
+            ("/service/Service/operation/Operation", "Service.Operation"),
+            ("/service/Service/operation/OtherOperation", "Service.OtherOperation"),
+            ("/service/com.example.Service/operation/Operation", "Service.Operation"),
+            ("/prefix/service/Service/operation/Operation?query", "Service.Operation"),
+        ] {
+            let route = router.match_route(&mut req(&Method::POST, uri, None)).await;
+            assert_eq!(route.unwrap(), expected, "uri: {uri}");
+        }
+    }
+
+    #[tokio::test]
+    async fn unknown_operation() {
+        let router = router();
+
+        for uri in [
+            "/service/Service/operation/MissingOperation",
+            "/service/OtherService/operation/Operation",
+        ] {
+            let res = router.match_route(&mut req(&Method::POST, uri, None)).await;
+            assert!(matches!(res, Err(Error::NotFound)), "uri: {uri}");
+        }
+
+        let res = router.match_route(&mut req(&Method::POST, "/", None)).await;
+        assert!(matches!(res, Err(Error::InvalidPath)));
+    }
+
+    #[tokio::test]
+    async fn wrong_method() {
+        let router = router();
+
+        for method in [Method::GET, Method::PUT, Method::DELETE] {
+            let res = router
+                .match_route(&mut req(&method, "/service/Service/operation/Operation", None))
+                .await;
+            assert!(matches!(res, Err(Error::MethodNotAllowed)), "method: {method}");
+        }
+    }
+
+    #[test]
+    fn errors_into_responses() {
+        let res = IntoResponse::<RpcV2>::into_response(Error::NotFound);
+        assert_eq!(res.status(), http::StatusCode::NOT_FOUND);
+        assert_eq!(res.headers()[SMITHY_PROTOCOL_HEADER], SMITHY_PROTOCOL_VALUE);
+        assert!(res.extensions().get::<RuntimeErrorExtension>().is_some());
+
+        let res = IntoResponse::<RpcV2>::into_response(Error::MethodNotAllowed);
+        assert_eq!(res.status(), http::StatusCode::METHOD_NOT_ALLOWED);
+    }
+
+    #[tokio::test]
+    async fn dispatch_through_tower_layer() {
+        use crate::routing::RoutingService;
+        use tower::util::MapRequestLayer;
+        use tower::{service_fn, ServiceExt};
+
+        let router: RpcV2Router<_> = ["Service.Operation"]
+            .into_iter()
+            .map(|key| {
+                let route = service_fn(|req: http::Request<hyper::Body>| async move {
+                    let mut res = http::Response::new(empty());
+                    if !req.headers().contains_key("x-layer") {
+                        *res.status_mut() = http::StatusCode::INTERNAL_SERVER_ERROR;
+                    }
+                    Ok::<_, Infallible>(res)
+                });
+                (key.to_string(), route)
+            })
+            .collect();
+        let svc = MapRequestLayer::new(|mut req: http::Request<hyper::Body>| {
+            req.headers_mut()
+                .insert("x-layer", http::HeaderValue::from_static("applied"));
+            req
+        })
+        .layer(RoutingService::<_, RpcV2>::new(router.boxed()));
+
+        let request = |uri| {
+            http::Request::post(uri)
+                .header(SMITHY_PROTOCOL_HEADER, SMITHY_PROTOCOL_VALUE)
+                .body(hyper::Body::empty())
+                .unwrap()
+        };
+
+        let res = svc
+            .clone()
+            .oneshot(request("/service/Service/operation/Operation"))
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), http::StatusCode::OK);
+
+        let res = svc
+            .oneshot(request("/service/Service/operation/MissingOperation"))
+            .await
+            .unwrap()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), http::StatusCode::NOT_FOUND);
+        assert_eq!(res.headers()[SMITHY_PROTOCOL_HEADER], SMITHY_PROTOCOL_VALUE);
+    }
+}
@@ -0,0 +1,124 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use super::rejection::{RequestRejection, ResponseRejection};
+use super::{RpcV2, SMITHY_PROTOCOL_HEADER, SMITHY_PROTOCOL_VALUE};
+use crate::extension::RuntimeErrorExtension;
+use crate::response::IntoResponse;
+use crate::runtime_error::{
+    InternalFailureException, PayloadTooLargeException, TimeoutException,
+    INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE,
+};
+use http::StatusCode;
+
+#[derive(Debug)]
+pub enum RuntimeError {
+    /// Request failed to deserialize or response failed to serialize.
+    Serialization(crate::Error),
+    /// As of writing, this variant can only occur upon failure to extract an
+    /// [`crate::extension::Extension`] from the request.
+    InternalFailure(crate::Error),
+    /// Request contained an `Accept` header with a MIME type, and the server cannot return a response
+    /// body adhering to that MIME type.
+    NotAcceptable,
+    /// The request does not contain the expected `Content-Type` header value.
+    UnsupportedMediaType,
+    /// The operation handler did not complete within its configured timeout.
+    Timeout,
+    /// The request does not contain a `smithy-protocol` header, or the header names a protocol
+    /// other than RPC v2 CBOR.
+    InvalidSmithyProtocol,
+    /// The request body is larger than the server is willing to buffer.
+    PayloadTooLarge,
+    /// Operation input contains data that does not adhere to the modeled [constraint traits].
+    /// [constraint traits]: <https://awslabs.github.io/smithy/2.0/spec/constraint-traits.html>
+    Validation(String),
+}
+
+impl RuntimeError {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Serialization(_) => "SerializationException",
+            Self::InternalFailure(_) => "InternalFailureException",
+            Self::NotAcceptable => "NotAcceptableException",
+            Self::UnsupportedMediaType => "UnsupportedMediaTypeException",
+            Self::Timeout => "TimeoutException",
+            Self::InvalidSmithyProtocol => "InvalidSmithyProtocolException",
+            Self::PayloadTooLarge => "PayloadTooLargeException",
+            Self::Validation(_) => "ValidationException",
+        }
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Serialization(_) => StatusCode::BAD_REQUEST,
+            Self::InternalFailure(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Self::NotAcceptable => StatusCode::NOT_ACCEPTABLE,
+            Self::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            Self::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            Self::InvalidSmithyProtocol => StatusCode::BAD_REQUEST,
+            Self::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            Self::Validation(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl IntoResponse<RpcV2> for InternalFailureException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<RpcV2>::into_response(RuntimeError::InternalFailure(crate::Error::new(String::new())))
+    }
+}
+
+impl IntoResponse<RpcV2> for TimeoutException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<RpcV2>::into_response(RuntimeError::Timeout)
+    }
+}
+
+impl IntoResponse<RpcV2> for PayloadTooLargeException {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        IntoResponse::<RpcV2>::into_response(RuntimeError::PayloadTooLarge)
+    }
+}
+
+impl IntoResponse<RpcV2> for RuntimeError {
+    fn into_response(self) -> http::Response<crate::body::BoxBody> {
+        let res = http::Response::builder()
+            .status(self.status_code())
+            .header(SMITHY_PROTOCOL_HEADER, SMITHY_PROTOCOL_VALUE)
+            .extension(RuntimeErrorExtension::new(self.name().to_string()));
+
+        // There is no CBOR encoder in this crate yet, so error bodies are left empty, except for
+        // validation errors, which carry the reason as plain text like in the other protocols.
+        let body = match self {
+            RuntimeError::Validation(reason) => crate::body::to_boxed(reason),
+            _ => crate::body::empty(),
+        };
+
+        res.body(body)
+            .expect(INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE)
+    }
+}
+
+impl From<ResponseRejection> for RuntimeError {
+    fn from(err: ResponseRejection) -> Self {
+        Self::Serialization(crate::Error::new(err))
+    }
+}
+
+impl From<RequestRejection> for RuntimeError {
+    fn from(err: RequestRejection) -> Self {
+        match err {
+            RequestRejection::MissingSmithyProtocolHeader | RequestRejection::UnsupportedSmithyProtocol(_) => {
+                Self::InvalidSmithyProtocol
+            }
+            RequestRejection::BodyTooLarge { .. } => Self::PayloadTooLarge,
+            RequestRejection::MissingContentType(_reason) => Self::UnsupportedMediaType,
+            RequestRejection::ConstraintViolation(reason) => Self::Validation(reason),
+            RequestRejection::NotAcceptable => Self::NotAcceptable,
+            _ => Self::Serialization(crate::Error::new(err)),
+        }
+    }
+}
@@ -17,6 +17,11 @@
 //! The [`ServerRequestId`] can be returned to the caller, who can in turn share the [`ServerRequestId`] to help the service owner in troubleshooting issues related to their usage of the service.
 //! Use [`ServerRequestIdProviderLayer::new_with_response_header`] to use [`ServerRequestId`] in your handler and add it to the response headers.
 //!
+//! If clients send their own request IDs, such as in an `x-amzn-request-id` header, use
+//! [`ServerRequestIdProviderLayer::with_request_header`] to use the client's request ID instead of generating one.
+//! A request ID is still generated for requests without one, and for requests whose ID is longer than 256 bytes or
+//! has characters other than visible ASCII, so that clients can't inject arbitrary data into logs.
+//!
 //! The [`ServerRequestId`] is not meant to be propagated to downstream dependencies of the service. You should rely on a distributed tracing implementation for correlation purposes (e.g. OpenTelemetry).
 //!
 //! ## Examples
@@ -63,12 +68,15 @@ use crate::{body::BoxBody, response::IntoResponse};
 
 use super::{internal_server_error, FromParts};
 
+/// The longest request ID, in bytes, that is accepted from a client.
+const MAX_CLIENT_REQUEST_ID_LEN: usize = 256;
+
 /// Opaque type for Server Request IDs.
 ///
 /// If it is missing, the request will be rejected with a `500 Internal Server Error` response.
 #[derive(Clone, Debug)]
 pub struct ServerRequestId {
-    id: Uuid,
+    id: String,
 }
 
 /// The server request ID has not been added to the [`Request`](http::Request) or has been previously removed.
@@ -79,11 +87,24 @@ pub struct MissingServerRequestId;
 
 impl ServerRequestId {
     pub fn new() -> Self {
-        Self { id: Uuid::new_v4() }
+        Self {
+            id: Uuid::new_v4().to_string(),
+        }
+    }
+
+    /// Uses a request ID sent by the client, if it is a non-empty string of at most
+    /// [`MAX_CLIENT_REQUEST_ID_LEN`] visible ASCII characters.
+    fn from_header(value: &HeaderValue) -> Option<Self> {
+        let id = value.as_bytes();
+        if id.is_empty() || id.len() > MAX_CLIENT_REQUEST_ID_LEN || !id.iter().all(u8::is_ascii_graphic) {
+            return None;
+        }
+        let id = std::str::from_utf8(id).expect("visible ASCII is valid UTF-8");
+        Some(Self { id: id.to_owned() })
     }
 
     pub(crate) fn to_header(&self) -> HeaderValue {
-        HeaderValue::from_str(&self.id.to_string()).expect("This string contains only valid ASCII")
+        HeaderValue::from_str(&self.id).expect("This string contains only valid ASCII")
     }
 }
 
@@ -111,6 +132,7 @@ impl Default for ServerRequestId {
 pub struct ServerRequestIdProvider<S> {
     inner: S,
     header_key: Option<HeaderName>,
+    request_header_key: Option<HeaderName>,
 }
 
 /// A layer that provides services with a unique request ID instance
@@ -118,21 +140,33 @@ pub struct ServerRequestIdProvider<S> {
 #[non_exhaustive]
 pub struct ServerRequestIdProviderLayer {
     header_key: Option<HeaderName>,
+    request_header_key: Option<HeaderName>,
 }
 
 impl ServerRequestIdProviderLayer {
     /// Generate a new unique request ID and do not add it as a response header
     /// Use [`ServerRequestIdProviderLayer::new_with_response_header`] to also add it as a response header
     pub fn new() -> Self {
-        Self { header_key: None }
+        Self {
+            header_key: None,
+            request_header_key: None,
+        }
     }
 
     /// Generate a new unique request ID and add it as a response header
     pub fn new_with_response_header(header_key: HeaderName) -> Self {
         Self {
             header_key: Some(header_key),
+            request_header_key: None,
         }
     }
+
+    /// Use the request ID sent by the client in the `request_header_key` request header, only generating a new
+    /// one if the header is missing or is not a non-empty string of at most 256 visible ASCII characters
+    pub fn with_request_header(mut self, request_header_key: HeaderName) -> Self {
+        self.request_header_key = Some(request_header_key);
+        self
+    }
 }
 
 impl Default for ServerRequestIdProviderLayer {
@@ -148,6 +182,7 @@ impl<S> Layer<S> for ServerRequestIdProviderLayer {
         ServerRequestIdProvider {
             inner,
             header_key: self.header_key.clone(),
+            request_header_key: self.request_header_key.clone(),
         }
     }
 }
@@ -166,7 +201,12 @@ where
     }
 
     fn call(&mut self, mut req: http::Request<Body>) -> Self::Future {
-        let request_id = ServerRequestId::new();
+        let request_id = self
+            .request_header_key
+            .as_ref()
+            .and_then(|key| req.headers().get(key))
+            .and_then(ServerRequestId::from_header)
+            .unwrap_or_default();
         match &self.header_key {
             Some(header_key) => {
                 req.extensions_mut().insert(request_id.clone());
@@ -274,4 +314,71 @@ mod tests {
 
         assert!(res.headers().is_empty());
     }
+
+    #[tokio::test]
+    async fn test_request_id_echoed_from_request_header() {
+        let request_id_header = HeaderName::from_static("x-amzn-request-id");
+        let svc = ServiceBuilder::new()
+            .layer(
+                &ServerRequestIdProviderLayer::new_with_response_header(request_id_header.clone())
+                    .with_request_header(request_id_header),
+            )
+            .service(service_fn(|req: Request<Body>| async move {
+                let request_id = req.extensions().get::<ServerRequestId>().unwrap();
+                assert_eq!(request_id.to_string(), "client-request-id");
+                Ok::<_, Infallible>(Response::new(BoxBody::default()))
+            }));
+
+        let req = http::Request::builder()
+            .header("x-amzn-request-id", "client-request-id")
+            .body(Body::empty())
+            .unwrap();
+
+        let res = svc.oneshot(req).await.unwrap();
+
+        assert_eq!(res.headers()["x-amzn-request-id"], "client-request-id");
+    }
+
+    #[tokio::test]
+    async fn test_request_id_echoed_on_error_response() {
+        use crate::protocol::rest_json_1::RestJson1;
+        use crate::runtime_error::TimeoutException;
+
+        let request_id_header = HeaderName::from_static("x-amzn-request-id");
+        let svc = ServiceBuilder::new()
+            .layer(
+                &ServerRequestIdProviderLayer::new_with_response_header(request_id_header.clone())
+                    .with_request_header(request_id_header),
+            )
+            .service(service_fn(|_req: Request<Body>| async move {
+                Ok::<_, Infallible>(IntoResponse::<RestJson1>::into_response(TimeoutException))
+            }));
+
+        let req = http::Request::builder()
+            .header("x-amzn-request-id", "client-request-id")
+            .body(Body::empty())
+            .unwrap();
+        let res = svc.clone().oneshot(req).await.unwrap();
+        assert_eq!(res.status(), http::StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(res.headers()["x-amzn-request-id"], "client-request-id");
+
+        // A request ID is generated when the client doesn't send one.
+        let res = svc.oneshot(Request::new(Body::empty())).await.unwrap();
+        assert_eq!(res.status(), http::StatusCode::GATEWAY_TIMEOUT);
+        assert!(!res.headers()["x-amzn-request-id"].is_empty());
+    }
+
+    #[test]
+    fn test_untrusted_request_ids_are_replaced() {
+        let accepted = |id: &[u8]| ServerRequestId::from_header(&HeaderValue::from_bytes(id).unwrap()).is_some();
+
+        assert!(accepted(b"client-request-id"));
+        assert!(accepted(&[b'a'; MAX_CLIENT_REQUEST_ID_LEN]));
+
+        assert!(!accepted(b""));
+        assert!(!accepted(&[b'a'; MAX_CLIENT_REQUEST_ID_LEN + 1]));
+        assert!(!accepted(b"client request id"));
+        assert!(!accepted(b"client\trequest-id"));
+        assert!(!accepted("client-request-id-\u{1F600}".as_bytes()));
+    }
 }
@@ -11,3 +11,13 @@
 pub struct InternalFailureException;
 
 pub const INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE: &str = "invalid HTTP response for `RuntimeError`; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues";
+
+/// A _protocol-agnostic_ type representing an operation handler that did not complete within its
+/// configured timeout. Like [`InternalFailureException`], it is converted into protocol-specific
+/// error variants, such as [`crate::protocol::rest_json_1::runtime_error::RuntimeError::Timeout`].
+pub struct TimeoutException;
+
+/// A _protocol-agnostic_ type representing a request whose body is larger than the configured
+/// limit. Like [`InternalFailureException`], it is converted into protocol-specific error variants,
+/// such as [`crate::protocol::rest_json_1::runtime_error::RuntimeError::PayloadTooLarge`].
+pub struct PayloadTooLargeException;